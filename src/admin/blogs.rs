@@ -0,0 +1,185 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params_map};
+use leptos_router::NavigateOptions;
+
+use super::fields::{OptionalTextField, StatusField, TextField};
+use super::{error_message, ShareLinkButton, NEW_ID};
+use crate::app::BlogDetail;
+use crate::auth::AdminSession;
+use crate::content::{admin_blog, admin_blogs, ContentKind, DeleteBlog, SaveBlog, ShareLink};
+use crate::data::{Blog, Status};
+use crate::routing::{BasePath, Route};
+
+#[component]
+pub fn BlogList() -> impl IntoView {
+    let blogs = Resource::new(|| (), |_| admin_blogs());
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"Blogs"</h1>
+            <A href=Route::AdminBlog(NEW_ID.into()).href(&base) attr:class="btn btn-primary">"New Post"</A>
+        </div>
+        <Suspense fallback=|| ()>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    let rows = blogs.await.unwrap_or_default().into_iter().map(|blog| {
+                        let href = Route::AdminBlog(blog.id.clone()).href(&base);
+                        view! {
+                            <tr>
+                                <td><A href=href>{blog.title}</A></td>
+                                <td><code>{blog.id}</code></td>
+                                <td>{blog.tag}</td>
+                                <td>{blog.status.label()}</td>
+                            </tr>
+                        }
+                    }).collect::<Vec<_>>();
+                    view! {
+                        <table class="admin-table">
+                            <thead><tr><th>"Title"</th><th>"Id"</th><th>"Tag"</th><th>"Status"</th></tr></thead>
+                            <tbody>{rows}</tbody>
+                        </table>
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn BlogEditor() -> impl IntoView {
+    let params = use_params_map();
+    let blog = Resource::new(
+        move || params.get().get("id").unwrap_or_default(),
+        |id| async move {
+            if id == NEW_ID {
+                Ok(Some(Blog {
+                    status: Status::Draft,
+                    ..Default::default()
+                }))
+            } else {
+                admin_blog(id).await
+            }
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || Suspend::new(async move {
+                match blog.await {
+                    Ok(Some(blog)) => {
+                        let original_id = (!blog.id.is_empty()).then(|| blog.id.to_string());
+                        view! { <BlogForm original_id blog/> }.into_any()
+                    }
+                    Ok(None) => view! { <p class="admin-error">"This post does not exist."</p> }.into_any(),
+                    Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn BlogForm(original_id: Option<String>, blog: Blog) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let navigate = use_navigate();
+
+    let draft = RwSignal::new(blog);
+    let save = ServerAction::<SaveBlog>::new();
+    let delete = ServerAction::<DeleteBlog>::new();
+    let is_new = original_id.is_none();
+    let history = original_id
+        .as_deref()
+        .map(|id| Route::AdminBlogHistory(id.into()).href(&base));
+    let saved_id = original_id.clone();
+
+    // Once a save lands, move to the editor URL for the (possibly renamed) id.
+    Effect::new({
+        let (base, navigate, original_id) = (base.clone(), navigate.clone(), original_id.clone());
+        move |_| {
+            if let Some(Ok(())) = save.value().get() {
+                let id = draft.with_untracked(|b| b.id.clone());
+                if original_id.as_deref() != Some(&*id) {
+                    navigate(
+                        &Route::AdminBlog(id).href(&base),
+                        NavigateOptions::default(),
+                    );
+                }
+            }
+        }
+    });
+    Effect::new({
+        let base = base.clone();
+        move |_| {
+            if let Some(Ok(())) = delete.value().get() {
+                navigate(&Route::AdminBlogs.href(&base), NavigateOptions::default());
+            }
+        }
+    });
+
+    let on_submit = {
+        let (csrf_token, original_id) = (admin.csrf_token.clone(), original_id.clone());
+        move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            save.dispatch(SaveBlog {
+                csrf_token: csrf_token.clone(),
+                original_id: original_id.clone(),
+                blog: draft.get_untracked(),
+            });
+        }
+    };
+    let on_delete = move |_| {
+        let Some(id) = original_id.clone() else {
+            return;
+        };
+        if window()
+            .confirm_with_message(&format!("Delete the post `{id}`?"))
+            .unwrap_or(false)
+        {
+            delete.dispatch(DeleteBlog {
+                csrf_token: admin.csrf_token.clone(),
+                id,
+            });
+        }
+    };
+    let status = move || match (save.value().get(), delete.value().get()) {
+        (_, Some(Err(err))) | (Some(Err(err)), _) => {
+            Some(view! { <p class="admin-error">{error_message(&err)}</p> }.into_any())
+        }
+        (Some(Ok(())), _) => Some(view! { <p class="admin-success">"Saved."</p> }.into_any()),
+        _ => None,
+    };
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>{move || draft.with(|b| if b.title.is_empty() { "New Post".to_string() } else { b.title.to_string() })}</h1>
+        </div>
+        <div class="admin-editor">
+            <form class="admin-form" on:submit=on_submit>
+                <TextField label="Id" value=Signal::derive(move || draft.with(|b| b.id.clone())) on_input=move |v| draft.update(|b| b.id = v)/>
+                <TextField label="Title" value=Signal::derive(move || draft.with(|b| b.title.clone())) on_input=move |v| draft.update(|b| b.title = v)/>
+                <TextField label="Subtitle" value=Signal::derive(move || draft.with(|b| b.subtitle.clone())) on_input=move |v| draft.update(|b| b.subtitle = v)/>
+                <TextField label="Tag" value=Signal::derive(move || draft.with(|b| b.tag.clone())) on_input=move |v| draft.update(|b| b.tag = v)/>
+                <StatusField value=Signal::derive(move || draft.with(|b| b.status)) on_change=move |status| draft.update(|b| b.status = status)/>
+                <TextField label="Description" multiline=true value=Signal::derive(move || draft.with(|b| b.description.clone())) on_input=move |v| draft.update(|b| b.description = v)/>
+                <TextField label="Overview" multiline=true value=Signal::derive(move || draft.with(|b| b.overview.clone())) on_input=move |v| draft.update(|b| b.overview = v)/>
+                <OptionalTextField label="Live Link" value=Signal::derive(move || draft.with(|b| b.live_link.clone())) on_input=move |v| draft.update(|b| b.live_link = v)/>
+                <div class="admin-actions">
+                    <button type="submit" class="btn btn-primary" disabled=move || save.pending().get()>"Save"</button>
+                    {(!is_new).then(|| view! {
+                        <button type="button" class="btn btn-secondary" on:click=on_delete>"Delete"</button>
+                    })}
+                    {history.map(|href| view! { <A href=href attr:class="btn btn-secondary">"History"</A> })}
+                </div>
+                {status}
+                {saved_id.map(|id| view! { <ShareLinkButton link=ShareLink::Preview kind=ContentKind::Blog id/> })}
+            </form>
+            <div class="admin-preview">
+                {move || view! { <BlogDetail blog=draft.get()/> }}
+            </div>
+        </div>
+    }
+}
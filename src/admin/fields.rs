@@ -0,0 +1,312 @@
+//! Form controls shared by the admin editors.
+//!
+//! Each control reads its value through a signal and reports edits through a callback,
+//! so it can be bound to one field of a larger draft record without re-rendering (and
+//! losing focus in) the rest of the form.
+
+use leptos::prelude::*;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::data::{Status, Text, Visibility};
+use crate::demos::{self, DEMOS};
+
+/// How `<input type="datetime-local">` formats its value.
+const DATETIME_LOCAL: &str = "%Y-%m-%dT%H:%M";
+
+#[component]
+pub fn TextField(
+    label: &'static str,
+    #[prop(into)] value: Signal<Text>,
+    #[prop(into)] on_input: Callback<Text>,
+    #[prop(optional)] multiline: bool,
+) -> impl IntoView {
+    let input = if multiline {
+        view! {
+            <textarea rows=5 prop:value=value on:input=move |ev| on_input.run(event_target_value(&ev).into())></textarea>
+        }
+        .into_any()
+    } else {
+        view! {
+            <input type="text" prop:value=value on:input=move |ev| on_input.run(event_target_value(&ev).into())/>
+        }
+        .into_any()
+    };
+
+    view! {
+        <label>
+            {label}
+            {input}
+        </label>
+    }
+}
+
+/// A text field for an optional value; clearing the input stores `None`.
+#[component]
+pub fn OptionalTextField(
+    label: &'static str,
+    #[prop(into)] value: Signal<Option<Text>>,
+    #[prop(into)] on_input: Callback<Option<Text>>,
+) -> impl IntoView {
+    view! {
+        <TextField
+            label
+            value=Signal::derive(move || value.get().unwrap_or_default())
+            on_input=move |text: Text| on_input.run((!text.trim().is_empty()).then_some(text))
+        />
+    }
+}
+
+/// Edits a structured value as JSON. Text that does not parse stays in the field with
+/// the error below it, and the last valid value is kept until it is fixed. Clearing the
+/// field stores the default value.
+#[component]
+pub fn JsonField<T>(
+    label: &'static str,
+    value: Signal<T>,
+    #[prop(into)] on_change: Callback<T>,
+    #[prop(optional)] hint: &'static str,
+) -> impl IntoView
+where
+    T: Serialize + DeserializeOwned + Default + PartialEq + Send + Sync + 'static,
+{
+    let initial = value.with_untracked(|value| {
+        if *value == T::default() {
+            String::new()
+        } else {
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        }
+    });
+    let error = RwSignal::new(None::<String>);
+    let on_input = move |ev| {
+        let text = event_target_value(&ev);
+        let parsed = if text.trim().is_empty() {
+            Ok(T::default())
+        } else {
+            serde_json::from_str(&text)
+        };
+        match parsed {
+            Ok(value) => {
+                error.set(None);
+                on_change.run(value);
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    };
+
+    view! {
+        <label>
+            {label}
+            <textarea class="admin-json" rows=12 spellcheck="false" prop:value=initial on:input=on_input></textarea>
+            {(!hint.is_empty()).then(|| view! { <span class="admin-hint">{hint}</span> })}
+            {move || error.get().map(|error| view! { <span class="admin-error">{error}</span> })}
+        </label>
+    }
+}
+
+/// Picks a publishing status, with a publish time (in UTC) for scheduled items.
+#[component]
+pub fn StatusField(
+    #[prop(into)] value: Signal<Status>,
+    #[prop(into)] on_change: Callback<Status>,
+) -> impl IntoView {
+    let on_select = move |ev| {
+        let status = match event_target_value(&ev).as_str() {
+            "draft" => Status::Draft,
+            "scheduled" => Status::Scheduled {
+                publish_at: Utc::now() + Duration::days(1),
+            },
+            "archived" => Status::Archived,
+            _ => Status::Published,
+        };
+        on_change.run(status);
+    };
+    let selected = move || match value.get() {
+        Status::Draft => "draft",
+        Status::Scheduled { .. } => "scheduled",
+        Status::Published => "published",
+        Status::Archived => "archived",
+    };
+
+    view! {
+        <label>
+            "Status"
+            <select prop:value=selected on:change=on_select>
+                <option value="draft">"Draft"</option>
+                <option value="scheduled">"Scheduled"</option>
+                <option value="published">"Published"</option>
+                <option value="archived">"Archived"</option>
+            </select>
+        </label>
+        {move || match value.get() {
+            Status::Scheduled { publish_at } => Some(view! {
+                <label>
+                    "Publish At (UTC)"
+                    <input
+                        type="datetime-local"
+                        prop:value=publish_at.format(DATETIME_LOCAL).to_string()
+                        on:change=move |ev| {
+                            if let Ok(time) = NaiveDateTime::parse_from_str(&event_target_value(&ev), DATETIME_LOCAL) {
+                                on_change.run(Status::Scheduled { publish_at: time.and_utc() });
+                            }
+                        }
+                    />
+                </label>
+            }),
+            _ => None,
+        }}
+    }
+}
+
+/// Picks whether a project is public or private.
+#[component]
+pub fn VisibilityField(
+    #[prop(into)] value: Signal<Visibility>,
+    #[prop(into)] on_change: Callback<Visibility>,
+) -> impl IntoView {
+    let on_select = move |ev| {
+        let visibility = match event_target_value(&ev).as_str() {
+            "private" => Visibility::Private,
+            _ => Visibility::Public,
+        };
+        on_change.run(visibility);
+    };
+    let selected = move || match value.get() {
+        Visibility::Public => "public",
+        Visibility::Private => "private",
+    };
+
+    view! {
+        <label>
+            "Visibility"
+            <select prop:value=selected on:change=on_select>
+                <option value="public">"Public"</option>
+                <option value="private">"Private (teaser only until unlocked)"</option>
+            </select>
+        </label>
+    }
+}
+
+/// Picks a demo from the registry. A key the registry does not know is kept as an option,
+/// so an edit elsewhere in the form does not drop it.
+#[component]
+pub fn DemoField(
+    #[prop(into)] value: Signal<Option<Text>>,
+    #[prop(into)] on_change: Callback<Option<Text>>,
+) -> impl IntoView {
+    let on_select = move |ev| {
+        let key = event_target_value(&ev);
+        on_change.run((!key.is_empty()).then(|| key.into()));
+    };
+    let unknown = move || value.get().filter(|key| demos::find(key).is_none());
+
+    view! {
+        <label>
+            "Demo"
+            <select prop:value=move || value.get().unwrap_or_default().to_string() on:change=on_select>
+                <option value="">"None"</option>
+                {DEMOS.iter().map(|demo| view! { <option value=demo.key>{demo.title}</option> }).collect_view()}
+                {move || unknown().map(|key| view! { <option value=key.to_string()>{format!("{key} (unknown)")}</option> })}
+            </select>
+        </label>
+    }
+}
+
+/// One editable property of a list item.
+pub struct Column<T> {
+    pub label: &'static str,
+    pub get: fn(&T) -> Text,
+    pub set: fn(&mut T, Text),
+}
+
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Column<T> {}
+
+type ListEdit<T> = Box<dyn FnOnce(&mut Vec<T>)>;
+
+/// Edits a list of records: one row of inputs per item, plus add and remove buttons.
+///
+/// Rows are keyed by position, so typing into a row only updates that input.
+#[component]
+pub fn ListField<T>(
+    label: &'static str,
+    #[prop(into)] items: Signal<Vec<T>>,
+    #[prop(into)] on_change: Callback<Vec<T>>,
+    columns: Vec<Column<T>>,
+) -> impl IntoView
+where
+    T: Clone + Default + Send + Sync + 'static,
+{
+    let columns = StoredValue::new(columns);
+    let update = move |edit: ListEdit<T>| {
+        let mut list = items.get_untracked();
+        edit(&mut list);
+        on_change.run(list);
+    };
+
+    view! {
+        <fieldset class="admin-list">
+            <legend>{label}</legend>
+            <For
+                each=move || 0..items.with(Vec::len)
+                key=|index| *index
+                children=move |index| {
+                    view! {
+                        <div class="admin-list-row">
+                            {columns.get_value().into_iter().map(|column| view! {
+                                <input
+                                    type="text"
+                                    placeholder=column.label
+                                    prop:value=move || items.with(|list| list.get(index).map(column.get).unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let text = Text::from(event_target_value(&ev));
+                                        update(Box::new(move |list| {
+                                            if let Some(item) = list.get_mut(index) {
+                                                (column.set)(item, text);
+                                            }
+                                        }));
+                                    }
+                                />
+                            }).collect::<Vec<_>>()}
+                            <button
+                                type="button"
+                                class="admin-icon-btn"
+                                aria-label="Remove"
+                                on:click=move |_| update(Box::new(move |list| {
+                                    if index < list.len() {
+                                        list.remove(index);
+                                    }
+                                }))
+                            >
+                                "×"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+            <button
+                type="button"
+                class="btn btn-secondary admin-add-btn"
+                on:click=move |_| update(Box::new(|list| list.push(T::default())))
+            >
+                "Add"
+            </button>
+        </fieldset>
+    }
+}
+
+/// Columns for editing a plain list of strings, such as technologies.
+pub fn string_columns(label: &'static str) -> Vec<Column<Text>> {
+    vec![Column {
+        label,
+        get: Text::clone,
+        set: |item, value| *item = value,
+    }]
+}
@@ -0,0 +1,275 @@
+//! Revision history with diffs and rollback, and the audit log.
+
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+use super::error_message;
+use crate::auth::AdminSession;
+use crate::content::{admin_audit_log, admin_history, ContentKind, Revision, Rollback};
+use crate::routing::{BasePath, Route};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[component]
+pub fn ProjectHistory() -> impl IntoView {
+    view! { <History kind=ContentKind::Project/> }
+}
+
+#[component]
+pub fn BlogHistory() -> impl IntoView {
+    view! { <History kind=ContentKind::Blog/> }
+}
+
+#[component]
+fn History(kind: ContentKind) -> impl IntoView {
+    let params = use_params_map();
+    let id = move || params.get().get("id").unwrap_or_default();
+    let history = Resource::new(id, move |id| admin_history(kind, id));
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let editor = move || Route::editor(kind, id()).href(&base);
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"History of " <code>{id}</code></h1>
+            <A href=editor attr:class="btn btn-secondary">"Back to Editor"</A>
+        </div>
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || Suspend::new(async move {
+                match history.await {
+                    Ok(revisions) if revisions.is_empty() => {
+                        view! { <p>"No changes have been recorded for this item yet."</p> }.into_any()
+                    }
+                    Ok(revisions) => view! {
+                        <Revisions kind id=id() revisions on_rollback=move || history.refetch()/>
+                    }
+                    .into_any(),
+                    Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+/// The revision table, plus a diff between any two revisions (by default the latest
+/// change).
+#[component]
+fn Revisions(
+    kind: ContentKind,
+    id: String,
+    revisions: Vec<Revision>,
+    #[prop(into)] on_rollback: Callback<()>,
+) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let rollback = ServerAction::<Rollback>::new();
+    Effect::new(move |_| {
+        if let Some(Ok(())) = rollback.value().get() {
+            on_rollback.run(());
+        }
+    });
+
+    let numbers: Vec<u64> = revisions.iter().map(|r| r.number).collect();
+    let to = RwSignal::new(numbers[0]);
+    let from = RwSignal::new(numbers.get(1).copied());
+    let revisions = StoredValue::new(revisions);
+    let older_than = move |number: u64| {
+        revisions.with_value(|all| all.iter().map(|r| r.number).find(|n| *n < number))
+    };
+
+    let rows = revisions.with_value(|all| {
+        all.iter()
+            .enumerate()
+            .map(|(index, revision)| {
+                let number = revision.number;
+                let can_restore = index > 0 && revision.record.is_some();
+                let (csrf_token, id) = (admin.csrf_token.clone(), id.clone());
+                let on_restore = move |_| {
+                    let confirmed = window()
+                        .confirm_with_message(&format!("Roll back `{id}` to revision #{number}?"))
+                        .unwrap_or(false);
+                    if confirmed {
+                        rollback.dispatch(Rollback {
+                            csrf_token: csrf_token.clone(),
+                            kind,
+                            id: id.clone(),
+                            number,
+                        });
+                    }
+                };
+                view! {
+                    <tr class:admin-row-selected=move || to.get() == number>
+                        <td>{format!("#{number}")}</td>
+                        <td>{revision.at.format(TIME_FORMAT).to_string()}</td>
+                        <td>{revision.author.clone()}</td>
+                        <td>{revision.summary()}</td>
+                        <td class="admin-row-actions">
+                            <button type="button" class="btn btn-secondary" on:click=move |_| {
+                                to.set(number);
+                                from.set(older_than(number));
+                            }>"Show Changes"</button>
+                            {can_restore.then(|| view! {
+                                <button
+                                    type="button"
+                                    class="btn btn-secondary"
+                                    disabled=move || rollback.pending().get()
+                                    on:click=on_restore
+                                >
+                                    "Roll Back"
+                                </button>
+                            })}
+                        </td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let options = move |selected: Signal<Option<u64>>| {
+        numbers
+            .iter()
+            .map(|number| {
+                let number = *number;
+                view! {
+                    <option value=number.to_string() selected=move || selected.get() == Some(number)>
+                        {format!("#{number}")}
+                    </option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let from_options = options(from.into());
+    let to_options = options(Signal::derive(move || Some(to.get())));
+
+    let lines = move |number: Option<u64>| {
+        revisions.with_value(|all| {
+            all.iter()
+                .find(|r| Some(r.number) == number)
+                .and_then(|r| r.record.as_ref())
+                .map(|record| record.lines())
+                .unwrap_or_default()
+        })
+    };
+    let diff = move || {
+        diff_lines(&lines(from.get()), &lines(Some(to.get())))
+            .into_iter()
+            .map(|(change, line)| {
+                let (class, marker) = match change {
+                    Change::Same => ("diff-same", "  "),
+                    Change::Added => ("diff-added", "+ "),
+                    Change::Removed => ("diff-removed", "- "),
+                };
+                view! { <span class=class>{marker}{line}</span> }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <table class="admin-table">
+            <thead><tr><th>"Revision"</th><th>"When"</th><th>"Author"</th><th>"Change"</th><th></th></tr></thead>
+            <tbody>{rows}</tbody>
+        </table>
+        {move || rollback.value().get().and_then(Result::err).map(|err| view! { <p class="admin-error">{error_message(&err)}</p> })}
+
+        <div class="admin-diff-controls">
+            <label>
+                "From"
+                <select on:change=move |ev| from.set(event_target_value(&ev).parse().ok())>
+                    <option value="" selected=move || from.get().is_none()>"(nothing)"</option>
+                    {from_options}
+                </select>
+            </label>
+            <label>
+                "To"
+                <select on:change=move |ev| {
+                    if let Ok(number) = event_target_value(&ev).parse() {
+                        to.set(number);
+                    }
+                }>
+                    {to_options}
+                </select>
+            </label>
+        </div>
+        <pre class="admin-diff">{diff}</pre>
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    Same,
+    Added,
+    Removed,
+}
+
+/// A line diff from the longest common subsequence. Records are a few dozen lines, so
+/// the quadratic table is no concern.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<(Change, String)> {
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push((Change::Same, old[i].clone()));
+            (i, j) = (i + 1, j + 1);
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            diff.push((Change::Removed, old[i].clone()));
+            i += 1;
+        } else {
+            diff.push((Change::Added, new[j].clone()));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| (Change::Removed, line.clone())));
+    diff.extend(new[j..].iter().map(|line| (Change::Added, line.clone())));
+    diff
+}
+
+#[component]
+pub fn AuditLog() -> impl IntoView {
+    let entries = Resource::new(|| (), |_| admin_audit_log());
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"Audit Log"</h1>
+        </div>
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    let entries = match entries.await {
+                        Ok(entries) => entries,
+                        Err(err) => return view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                    };
+                    let rows = entries.into_iter().map(|entry| {
+                        let history = entry.action.target().map(|(kind, id)| Route::history(kind, id.clone()).href(&base));
+                        view! {
+                            <tr>
+                                <td>{entry.at.format(TIME_FORMAT).to_string()}</td>
+                                <td>{entry.actor}</td>
+                                <td>{entry.action.describe()}</td>
+                                <td>{history.map(|href| view! { <A href=href>"History"</A> })}</td>
+                            </tr>
+                        }
+                    }).collect::<Vec<_>>();
+                    view! {
+                        <table class="admin-table">
+                            <thead><tr><th>"When"</th><th>"Who"</th><th>"What"</th><th></th></tr></thead>
+                            <tbody>{rows}</tbody>
+                        </table>
+                    }
+                    .into_any()
+                })
+            }}
+        </Suspense>
+    }
+}
@@ -0,0 +1,209 @@
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{FormData, HtmlFormElement};
+use leptos_router::components::A;
+
+use super::error_message;
+use crate::auth::AdminSession;
+use crate::content::{
+    admin_media, upload_media, DeleteMedia, MediaEntry, MediaKind, MediaUse, UpdateMedia,
+};
+use crate::routing::{BasePath, Route};
+
+const ACCEPTED: &str = "image/jpeg,image/png,image/webp,image/gif,application/pdf";
+
+#[component]
+pub fn MediaLibrary() -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let library = Resource::new(|| (), |_| admin_media());
+    // File inputs can only be sent as browser `FormData`, which is not `Send`.
+    let upload = Action::new_local(|data: &FormData| upload_media(data.clone().into()));
+    Effect::new(move |_| {
+        if let Some(Ok(_)) = upload.value().get() {
+            library.refetch();
+        }
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let Some(form) = ev
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlFormElement>().ok())
+        else {
+            return;
+        };
+        if let Ok(data) = FormData::new_with_form(&form) {
+            upload.dispatch_local(data);
+            form.reset();
+        }
+    };
+    let upload_status = move || {
+        upload.value().get().map(|result| match result {
+            Ok(items) => view! { <p class="admin-success">{format!("Uploaded {} file(s).", items.len())}</p> }.into_any(),
+            Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"Media"</h1>
+        </div>
+        <form class="admin-form admin-upload" on:submit=on_submit>
+            // Must come before the files: the server checks it before reading them.
+            <input type="hidden" name="csrf_token" value=admin.csrf_token/>
+            <label>
+                "Upload images or PDFs"
+                <input type="file" name="files" accept=ACCEPTED multiple required/>
+            </label>
+            <p class="admin-hint">
+                "Images are scaled to fit " {crate::content::MAX_IMAGE_SIDE} " pixels and saved as JPEG, or PNG if they have transparency."
+            </p>
+            <div class="admin-actions">
+                <button type="submit" class="btn btn-primary" disabled=move || upload.pending().get()>
+                    {move || if upload.pending().get() { "Uploading…" } else { "Upload" }}
+                </button>
+            </div>
+            {upload_status}
+        </form>
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || Suspend::new(async move {
+                match library.await {
+                    Ok(entries) if entries.is_empty() => view! { <p>"Nothing has been uploaded yet."</p> }.into_any(),
+                    Ok(entries) => view! {
+                        <div class="media-grid">
+                            {entries.into_iter().map(|entry| view! {
+                                <MediaCard entry on_change=move || library.refetch()/>
+                            }).collect_view()}
+                        </div>
+                    }
+                    .into_any(),
+                    Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn MediaCard(entry: MediaEntry, #[prop(into)] on_change: Callback<()>) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let MediaEntry { item, uses } = entry;
+
+    let caption = RwSignal::new(item.caption.to_string());
+    let alt = RwSignal::new(item.alt.to_string());
+    let update = ServerAction::<UpdateMedia>::new();
+    let delete = ServerAction::<DeleteMedia>::new();
+    Effect::new(move |_| {
+        if let Some(Ok(())) = delete.value().get() {
+            on_change.run(());
+        }
+    });
+
+    let preview = match (item.kind, item.thumbnail_url()) {
+        (MediaKind::Image, Some(thumbnail)) => {
+            view! { <img src=base.path(&thumbnail) alt=item.alt.clone() loading="lazy"/> }
+                .into_any()
+        }
+        _ => view! { <span class="media-file-icon">"PDF"</span> }.into_any(),
+    };
+    let details = match (item.width, item.height) {
+        (Some(width), Some(height)) => format!("{width}×{height} · {}", format_size(item.size)),
+        _ => format_size(item.size),
+    };
+    let in_use = !uses.is_empty();
+    let uses_view = uses
+        .into_iter()
+        .map(|place| match place {
+            MediaUse::Item { kind, id, title } => {
+                let href = Route::editor(kind, id).href(&base);
+                view! { <li><A href=href>{title}</A></li> }.into_any()
+            }
+            MediaUse::Gallery => {
+                view! { <li><A href=Route::AdminPhotos.href(&base)>"Photo gallery"</A></li> }
+                    .into_any()
+            }
+        })
+        .collect_view();
+
+    let on_save = {
+        let (csrf_token, id) = (admin.csrf_token.clone(), item.id.to_string());
+        move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            update.dispatch(UpdateMedia {
+                csrf_token: csrf_token.clone(),
+                id: id.clone(),
+                caption: caption.get_untracked(),
+                alt: alt.get_untracked(),
+            });
+        }
+    };
+    let on_delete = {
+        let (csrf_token, id) = (admin.csrf_token.clone(), item.id.to_string());
+        move |_| {
+            if window()
+                .confirm_with_message(&format!("Delete `{id}`?"))
+                .unwrap_or(false)
+            {
+                delete.dispatch(DeleteMedia {
+                    csrf_token: csrf_token.clone(),
+                    id: id.clone(),
+                });
+            }
+        }
+    };
+    let status = move || match (update.value().get(), delete.value().get()) {
+        (_, Some(Err(err))) | (Some(Err(err)), _) => {
+            Some(view! { <p class="admin-error">{error_message(&err)}</p> }.into_any())
+        }
+        (Some(Ok(())), _) => Some(view! { <p class="admin-success">"Saved."</p> }.into_any()),
+        _ => None,
+    };
+
+    view! {
+        <div class="media-card">
+            <a class="media-preview" href=base.path(&item.url()) target="_blank">{preview}</a>
+            <form class="admin-form" on:submit=on_save>
+                <code class="media-name" title=item.original_name.clone()>{item.id.clone()}</code>
+                <span class="admin-hint">{details}</span>
+                <input class="admin-preview-link" type="text" readonly prop:value=item.url() title="URL to use in content"/>
+                <label>
+                    "Caption"
+                    <input type="text" prop:value=move || caption.get() on:input=move |ev| caption.set(event_target_value(&ev))/>
+                </label>
+                <label>
+                    "Alt Text"
+                    <input type="text" prop:value=move || alt.get() on:input=move |ev| alt.set(event_target_value(&ev))/>
+                </label>
+                <div class="media-uses">
+                    {if in_use {
+                        view! { <span>"Used in:"</span><ul>{uses_view}</ul> }.into_any()
+                    } else {
+                        view! { <span>"Not used anywhere."</span> }.into_any()
+                    }}
+                </div>
+                <div class="admin-actions">
+                    <button type="submit" class="btn btn-primary" disabled=move || update.pending().get()>"Save"</button>
+                    <button
+                        type="button"
+                        class="btn btn-secondary"
+                        disabled=in_use
+                        title=in_use.then_some("Remove it from the content that uses it first.")
+                        on:click=on_delete
+                    >
+                        "Delete"
+                    </button>
+                </div>
+                {status}
+            </form>
+        </div>
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1_024 => format!("{bytes} B"),
+        1_024..1_048_576 => format!("{:.0} KB", bytes as f64 / 1_024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,237 @@
+//! The `/admin` area.
+//!
+//! [`AdminLayout`] only renders its child routes for a signed-in administrator and
+//! provides the [`AdminSession`] to them through context. The server functions those
+//! pages call check the session again, so the guard here is purely for navigation.
+
+mod blogs;
+mod fields;
+mod history;
+mod media;
+mod photos;
+mod projects;
+
+use leptos::prelude::*;
+use leptos_router::components::{Outlet, Redirect, A};
+
+use crate::auth::{current_admin, AdminSession, Login, Logout};
+use crate::content::{
+    admin_blogs, admin_projects, list_photos, ContentKind, CreateShareLink, ShareLink,
+};
+use crate::routing::{BasePath, Route};
+
+pub use blogs::{BlogEditor, BlogList};
+pub use history::{AuditLog, BlogHistory, ProjectHistory};
+pub use media::MediaLibrary;
+pub use photos::PhotoEditor;
+pub use projects::{ProjectEditor, ProjectList};
+
+/// Route segment used in editor URLs for a record that has not been saved yet.
+pub const NEW_ID: &str = "new";
+
+/// Strips the transport prefix `ServerFnError` adds, leaving the server's own message.
+pub fn error_message(err: &ServerFnError) -> String {
+    match err {
+        ServerFnError::ServerError(msg) => msg.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[component]
+pub fn AdminLogin() -> impl IntoView {
+    let login = ServerAction::<Login>::new();
+    let error = move || {
+        login
+            .value()
+            .get()
+            .and_then(|result| result.err())
+            .map(|err| error_message(&err))
+    };
+
+    view! {
+        <section class="admin-login container">
+            <div class="admin-card">
+                <h1>"Admin Login"</h1>
+                <ActionForm action=login attr:class="admin-form">
+                    <label>
+                        "Username"
+                        <input type="text" name="username" autocomplete="username" required/>
+                    </label>
+                    <label>
+                        "Password"
+                        <input type="password" name="password" autocomplete="current-password" required/>
+                    </label>
+                    <button type="submit" class="btn btn-primary" disabled=move || login.pending().get()>
+                        "Sign In"
+                    </button>
+                </ActionForm>
+                {move || error().map(|msg| view! { <p class="admin-error">{msg}</p> })}
+            </div>
+        </section>
+    }
+}
+
+#[component]
+pub fn AdminLayout() -> impl IntoView {
+    let session = Resource::new(|| (), |_| current_admin());
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let login_path = Route::AdminLogin.href(&base);
+
+    view! {
+        <Suspense fallback=|| view! { <p class="admin-status">"Checking session…"</p> }>
+            {
+                let login_path = login_path.clone();
+                move || {
+                    let login_path = login_path.clone();
+                    Suspend::new(async move {
+                        match session.await {
+                            Ok(Some(_)) if cfg!(feature = "islands") => view! {
+                                <section class="admin-login container">
+                                    <div class="admin-card">
+                                        <h1>"Admin Unavailable"</h1>
+                                        <p>"The admin area needs the regular build; the islands build only hydrates the public pages' interactive parts."</p>
+                                    </div>
+                                </section>
+                            }.into_any(),
+                            Ok(Some(admin)) => view! { <AdminShell admin/> }.into_any(),
+                            Ok(None) => view! { <Redirect path=login_path/> }.into_any(),
+                            Err(_) => view! {
+                                <section class="admin-login container">
+                                    <div class="admin-card">
+                                        <h1>"Admin Unavailable"</h1>
+                                        <p>"The admin area is only available when the site is served by the portfolio server."</p>
+                                    </div>
+                                </section>
+                            }.into_any(),
+                        }
+                    })
+                }
+            }
+        </Suspense>
+    }
+}
+
+#[component]
+fn AdminShell(admin: AdminSession) -> impl IntoView {
+    provide_context(admin.clone());
+    let logout = ServerAction::<Logout>::new();
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+
+    view! {
+        <div class="admin">
+            <header class="admin-header">
+                <A href=Route::Admin.href(&base) attr:class="admin-brand">"Portfolio Admin"</A>
+                <nav class="admin-nav">
+                    <A href=Route::AdminProjects.href(&base)>"Projects"</A>
+                    <A href=Route::AdminBlogs.href(&base)>"Blogs"</A>
+                    <A href=Route::AdminPhotos.href(&base)>"Photos"</A>
+                    <A href=Route::AdminMedia.href(&base)>"Media"</A>
+                    <A href=Route::AdminAudit.href(&base)>"Audit Log"</A>
+                </nav>
+                <div class="admin-user">
+                    <span>{admin.username}</span>
+                    <ActionForm action=logout>
+                        <input type="hidden" name="csrf_token" value=admin.csrf_token/>
+                        <button type="submit" class="btn btn-secondary">"Log Out"</button>
+                    </ActionForm>
+                </div>
+            </header>
+            <div class="admin-main container">
+                <Outlet/>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn AdminDashboard() -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let counts = Resource::new(
+        || (),
+        |_| async move {
+            let projects = admin_projects().await?.len();
+            let blogs = admin_blogs().await?.len();
+            let photos = list_photos().await?.len();
+            Ok::<_, ServerFnError>((projects, blogs, photos))
+        },
+    );
+
+    view! {
+        <h1>"Dashboard"</h1>
+        <p>"Signed in as " <strong>{admin.username}</strong> "."</p>
+        <Suspense fallback=|| ()>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    let (projects, blogs, photos) = counts.await.unwrap_or_default();
+                    view! {
+                        <div class="admin-tiles">
+                            <A href=Route::AdminProjects.href(&base) attr:class="project-card">
+                                <h3>"Projects"</h3>
+                                <p>{format!("{projects} projects")}</p>
+                            </A>
+                            <A href=Route::AdminBlogs.href(&base) attr:class="project-card">
+                                <h3>"Blogs"</h3>
+                                <p>{format!("{blogs} posts")}</p>
+                            </A>
+                            <A href=Route::AdminPhotos.href(&base) attr:class="project-card">
+                                <h3>"Photos"</h3>
+                                <p>{format!("{photos} photos")}</p>
+                            </A>
+                        </div>
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
+
+/// Creates a signed [`ShareLink`] to the saved version of an item: a preview for
+/// reviewers before it is published, or an invite to a private project.
+#[component]
+fn ShareLinkButton(link: ShareLink, kind: ContentKind, id: String) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let create = ServerAction::<CreateShareLink>::new();
+
+    let created = move || {
+        create.value().get().map(|result| match result {
+            Ok(path) => {
+                let origin = window().location().origin().unwrap_or_default();
+                let url = format!("{origin}{}", base.path(&path));
+                view! {
+                    <input class="admin-preview-link" type="text" readonly prop:value=url on:focus=|ev| {
+                        use leptos::wasm_bindgen::JsCast;
+                        if let Some(input) = ev.target().and_then(|t| t.dyn_into::<leptos::web_sys::HtmlInputElement>().ok()) {
+                            input.select();
+                        }
+                    }/>
+                }
+                .into_any()
+            }
+            Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <div class="admin-preview-share">
+            <button
+                type="button"
+                class="btn btn-secondary"
+                disabled=move || create.pending().get()
+                on:click=move |_| {
+                    create.dispatch(CreateShareLink {
+                        csrf_token: admin.csrf_token.clone(),
+                        link,
+                        kind,
+                        id: id.clone(),
+                    });
+                }
+            >
+                {link.label()}
+            </button>
+            {created}
+        </div>
+    }
+}
@@ -0,0 +1,72 @@
+use leptos::prelude::*;
+
+use super::error_message;
+use super::fields::ListField;
+use super::projects::photo_columns;
+use crate::app::PhotoCard;
+use crate::auth::AdminSession;
+use crate::content::{list_photos, SavePhotos};
+use crate::data::Photo;
+
+/// Edits the gallery shown on the Photos page.
+#[component]
+pub fn PhotoEditor() -> impl IntoView {
+    let photos = Resource::new(|| (), |_| list_photos());
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"Photos"</h1>
+        </div>
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || Suspend::new(async move {
+                match photos.await {
+                    Ok(photos) => view! { <PhotoForm photos/> }.into_any(),
+                    Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn PhotoForm(photos: Vec<Photo>) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let draft = RwSignal::new(photos);
+    let save = ServerAction::<SavePhotos>::new();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        save.dispatch(SavePhotos {
+            csrf_token: admin.csrf_token.clone(),
+            photos: draft.get_untracked(),
+        });
+    };
+    let status = move || {
+        save.value().get().map(|result| match result {
+            Ok(()) => view! { <p class="admin-success">"Saved."</p> }.into_any(),
+            Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <div class="admin-editor">
+            <form class="admin-form" on:submit=on_submit>
+                <ListField
+                    label="Gallery"
+                    items=draft
+                    on_change=move |list| draft.set(list)
+                    columns=photo_columns()
+                />
+                <div class="admin-actions">
+                    <button type="submit" class="btn btn-primary" disabled=move || save.pending().get()>"Save"</button>
+                </div>
+                {status}
+            </form>
+            <div class="admin-preview">
+                <div class="photos-grid">
+                    {move || draft.get().into_iter().map(|photo| view! { <PhotoCard photo/> }).collect::<Vec<_>>()}
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params_map};
+use leptos_router::NavigateOptions;
+
+use super::fields::{
+    string_columns, Column, DemoField, JsonField, ListField, OptionalTextField, StatusField,
+    TextField, VisibilityField,
+};
+use super::{error_message, ShareLinkButton, NEW_ID};
+use crate::app::ProjectDetail;
+use crate::auth::AdminSession;
+use crate::content::{
+    admin_project, admin_projects, ContentKind, DeleteProject, SaveProject, ShareLink,
+};
+use crate::data::{Block, Photo, Poster, Project, Status, Text};
+use crate::routing::{BasePath, Route};
+
+const BLOCKS_HINT: &str = r#"Optional sections shown after the fields above, as a JSON list such as [{"type": "rich_text", "heading": "Results", "body": "…"}]. Types: rich_text, image, gallery, code, quote, metrics, video and callout."#;
+
+#[component]
+pub fn ProjectList() -> impl IntoView {
+    let projects = Resource::new(|| (), |_| admin_projects());
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>"Projects"</h1>
+            <A href=Route::AdminProject(NEW_ID.into()).href(&base) attr:class="btn btn-primary">"New Project"</A>
+        </div>
+        <Suspense fallback=|| ()>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    let rows = projects.await.unwrap_or_default().into_iter().map(|project| {
+                        let href = Route::AdminProject(project.id.clone()).href(&base);
+                        let private = project.is_private().then_some(" · Private");
+                        view! {
+                            <tr>
+                                <td><A href=href>{project.title}</A></td>
+                                <td><code>{project.id}</code></td>
+                                <td>{project.tag}</td>
+                                <td>{project.status.label()}{private}</td>
+                            </tr>
+                        }
+                    }).collect::<Vec<_>>();
+                    view! {
+                        <table class="admin-table">
+                            <thead><tr><th>"Title"</th><th>"Id"</th><th>"Tag"</th><th>"Status"</th></tr></thead>
+                            <tbody>{rows}</tbody>
+                        </table>
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn ProjectEditor() -> impl IntoView {
+    let params = use_params_map();
+    let project = Resource::new(
+        move || params.get().get("id").unwrap_or_default(),
+        |id| async move {
+            if id == NEW_ID {
+                Ok(Some(Project {
+                    status: Status::Draft,
+                    ..Default::default()
+                }))
+            } else {
+                admin_project(id).await
+            }
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <p class="admin-status">"Loading…"</p> }>
+            {move || Suspend::new(async move {
+                match project.await {
+                    Ok(Some(project)) => {
+                        let original_id = (!project.id.is_empty()).then(|| project.id.to_string());
+                        view! { <ProjectForm original_id project/> }.into_any()
+                    }
+                    Ok(None) => view! { <p class="admin-error">"This project does not exist."</p> }.into_any(),
+                    Err(err) => view! { <p class="admin-error">{error_message(&err)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn ProjectForm(original_id: Option<String>, project: Project) -> impl IntoView {
+    let admin = use_context::<AdminSession>().expect("AdminSession context not found");
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let navigate = use_navigate();
+
+    let draft = RwSignal::new(project);
+    // Sent alongside the draft, never stored in it: admins only ever set a passphrase.
+    let passphrase = RwSignal::new(String::new());
+    let save = ServerAction::<SaveProject>::new();
+    let delete = ServerAction::<DeleteProject>::new();
+    let is_new = original_id.is_none();
+    let history = original_id
+        .as_deref()
+        .map(|id| Route::AdminProjectHistory(id.into()).href(&base));
+    let saved_id = original_id.clone();
+
+    // Once a save lands, move to the editor URL for the (possibly renamed) id.
+    Effect::new({
+        let (base, navigate, original_id) = (base.clone(), navigate.clone(), original_id.clone());
+        move |_| {
+            if let Some(Ok(())) = save.value().get() {
+                passphrase.set(String::new());
+                let id = draft.with_untracked(|p| p.id.clone());
+                if original_id.as_deref() != Some(&*id) {
+                    navigate(
+                        &Route::AdminProject(id).href(&base),
+                        NavigateOptions::default(),
+                    );
+                }
+            }
+        }
+    });
+    Effect::new({
+        let base = base.clone();
+        move |_| {
+            if let Some(Ok(())) = delete.value().get() {
+                navigate(
+                    &Route::AdminProjects.href(&base),
+                    NavigateOptions::default(),
+                );
+            }
+        }
+    });
+
+    let on_submit = {
+        let (csrf_token, original_id) = (admin.csrf_token.clone(), original_id.clone());
+        move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            save.dispatch(SaveProject {
+                csrf_token: csrf_token.clone(),
+                original_id: original_id.clone(),
+                project: draft.get_untracked(),
+                passphrase: passphrase.with_untracked(|p| (!p.is_empty()).then(|| p.clone())),
+            });
+        }
+    };
+    let on_delete = move |_| {
+        let Some(id) = original_id.clone() else {
+            return;
+        };
+        if window()
+            .confirm_with_message(&format!("Delete the project `{id}`?"))
+            .unwrap_or(false)
+        {
+            delete.dispatch(DeleteProject {
+                csrf_token: admin.csrf_token.clone(),
+                id,
+            });
+        }
+    };
+    let status = move || match (save.value().get(), delete.value().get()) {
+        (_, Some(Err(err))) | (Some(Err(err)), _) => {
+            Some(view! { <p class="admin-error">{error_message(&err)}</p> }.into_any())
+        }
+        (Some(Ok(())), _) => Some(view! { <p class="admin-success">"Saved."</p> }.into_any()),
+        _ => None,
+    };
+
+    view! {
+        <div class="admin-toolbar">
+            <h1>{move || draft.with(|p| if p.title.is_empty() { "New Project".to_string() } else { p.title.to_string() })}</h1>
+        </div>
+        <div class="admin-editor">
+            <form class="admin-form" on:submit=on_submit>
+                <TextField label="Id" value=Signal::derive(move || draft.with(|p| p.id.clone())) on_input=move |v| draft.update(|p| p.id = v)/>
+                <TextField label="Title" value=Signal::derive(move || draft.with(|p| p.title.clone())) on_input=move |v| draft.update(|p| p.title = v)/>
+                <TextField label="Subtitle" value=Signal::derive(move || draft.with(|p| p.subtitle.clone())) on_input=move |v| draft.update(|p| p.subtitle = v)/>
+                <TextField label="Tag" value=Signal::derive(move || draft.with(|p| p.tag.clone())) on_input=move |v| draft.update(|p| p.tag = v)/>
+                <StatusField value=Signal::derive(move || draft.with(|p| p.status)) on_change=move |status| draft.update(|p| p.status = status)/>
+                <VisibilityField value=Signal::derive(move || draft.with(|p| p.visibility)) on_change=move |visibility| draft.update(|p| p.visibility = visibility)/>
+                {move || draft.with(Project::is_private).then(|| view! {
+                    <label>
+                        "New Passphrase"
+                        <input
+                            type="password"
+                            autocomplete="new-password"
+                            placeholder=if is_new { "Required to unlock without an invite" } else { "Leave blank to keep the current one" }
+                            prop:value=move || passphrase.get()
+                            on:input=move |ev| passphrase.set(event_target_value(&ev))
+                        />
+                    </label>
+                })}
+                <TextField label="Description" multiline=true value=Signal::derive(move || draft.with(|p| p.description.clone())) on_input=move |v| draft.update(|p| p.description = v)/>
+                <TextField label="Overview" multiline=true value=Signal::derive(move || draft.with(|p| p.overview.clone())) on_input=move |v| draft.update(|p| p.overview = v)/>
+                <TextField label="My Role" multiline=true value=Signal::derive(move || draft.with(|p| p.role.clone())) on_input=move |v| draft.update(|p| p.role = v)/>
+                <ListField
+                    label="Technologies"
+                    items=Signal::derive(move || draft.with(|p| p.technologies.as_deref().unwrap_or_default().to_vec()))
+                    on_change={move |list: Vec<Text>| draft.update(|p| p.technologies = (!list.is_empty()).then(|| list.into()))}
+                    columns=string_columns("Technology")
+                />
+                <OptionalTextField label="Live Site" value=Signal::derive(move || draft.with(|p| p.live_link.clone())) on_input=move |v| draft.update(|p| p.live_link = v)/>
+                <OptionalTextField label="Code" value=Signal::derive(move || draft.with(|p| p.code_link.clone())) on_input=move |v| draft.update(|p| p.code_link = v)/>
+                <OptionalTextField label="Paper" value=Signal::derive(move || draft.with(|p| p.paper_link.clone())) on_input=move |v| draft.update(|p| p.paper_link = v)/>
+                <ListField
+                    label="Posters"
+                    items=Signal::derive(move || draft.with(|p| p.posters.as_deref().unwrap_or_default().to_vec()))
+                    on_change={move |list: Vec<Poster>| draft.update(|p| p.posters = (!list.is_empty()).then(|| list.into()))}
+                    columns=poster_columns()
+                />
+                <ListField
+                    label="Photos"
+                    items=Signal::derive(move || draft.with(|p| p.photos.as_deref().unwrap_or_default().to_vec()))
+                    on_change={move |list: Vec<Photo>| draft.update(|p| p.photos = (!list.is_empty()).then(|| list.into()))}
+                    columns=photo_columns()
+                />
+                <JsonField
+                    label="Content Blocks"
+                    hint=BLOCKS_HINT
+                    value=Signal::derive(move || draft.with(|p| p.blocks.clone()))
+                    on_change={move |blocks: Option<Arc<[Block]>>| draft.update(|p| p.blocks = blocks.filter(|b| !b.is_empty()))}
+                />
+                <DemoField value=Signal::derive(move || draft.with(|p| p.demo.clone())) on_change=move |demo| draft.update(|p| p.demo = demo)/>
+                <div class="admin-actions">
+                    <button type="submit" class="btn btn-primary" disabled=move || save.pending().get()>"Save"</button>
+                    {(!is_new).then(|| view! {
+                        <button type="button" class="btn btn-secondary" on:click=on_delete>"Delete"</button>
+                    })}
+                    {history.map(|href| view! { <A href=href attr:class="btn btn-secondary">"History"</A> })}
+                </div>
+                {status}
+                {saved_id.map(|id| view! {
+                    <ShareLinkButton link=ShareLink::Preview kind=ContentKind::Project id=id.clone()/>
+                    {move || draft.with(Project::is_private).then(|| view! {
+                        <ShareLinkButton link=ShareLink::Invite kind=ContentKind::Project id=id.clone()/>
+                    })}
+                })}
+            </form>
+            <div class="admin-preview">
+                {move || view! { <ProjectDetail project=draft.get()/> }}
+            </div>
+        </div>
+    }
+}
+
+fn poster_columns() -> Vec<Column<Poster>> {
+    vec![
+        Column {
+            label: "Name",
+            get: |p| p.name.clone(),
+            set: |p, v| p.name = v,
+        },
+        Column {
+            label: "URL",
+            get: |p| p.url.clone(),
+            set: |p, v| p.url = v,
+        },
+        Column {
+            label: "Preview Image",
+            get: |p| p.preview.clone().unwrap_or_default(),
+            set: |p, v| p.preview = (!v.is_empty()).then_some(v),
+        },
+        Column {
+            label: "Pages",
+            get: |p| p.pages.map(|n| n.to_string().into()).unwrap_or_default(),
+            set: |p, v| p.pages = v.trim().parse().ok(),
+        },
+    ]
+}
+
+pub(super) fn photo_columns() -> Vec<Column<Photo>> {
+    vec![
+        Column {
+            label: "URL",
+            get: |p| p.url.clone(),
+            set: |p, v| p.url = v,
+        },
+        Column {
+            label: "Caption",
+            get: |p| p.caption.clone(),
+            set: |p, v| p.caption = v,
+        },
+        Column {
+            label: "Alt Text",
+            get: |p| p.alt.clone(),
+            set: |p, v| p.alt = v,
+        },
+    ]
+}
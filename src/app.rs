@@ -1,28 +1,47 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::time::Duration;
+
 use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
 use leptos_router::{
-    components::{A, Route, Router, Routes},
-    hooks::{use_location, use_params_map},
-    path,
+    components::{ParentRoute, Route, Router, Routes, RoutingProgress, A},
+    hooks::{use_location, use_params_map, use_query_map},
+    path, Lazy, LazyRoute,
 };
 
-use crate::data::{get_project_by_id, get_projects, Project, get_blog_by_id, get_blogs, Blog, get_photos, Photo};
-
-#[derive(Copy, Clone, Debug)]
-struct LightboxState(WriteSignal<Option<String>>);
+use crate::admin::{
+    AdminDashboard, AdminLayout, AdminLogin, AuditLog, BlogEditor, BlogHistory, BlogList,
+    MediaLibrary, PhotoEditor, ProjectEditor, ProjectHistory, ProjectList,
+};
+use crate::blocks::Blocks;
+use crate::content::{
+    fetch_blog, fetch_blogs, fetch_photos, fetch_project, fetch_projects, ProjectPage,
+};
+use crate::data::{Blog, Photo, Poster, Project, Status, Text};
+use crate::demos::{self, DemoSection};
+use crate::routing::{BasePath, Route, Section};
+
+/// What the lightbox overlay is showing.
+#[derive(Clone, Debug, PartialEq)]
+enum LightboxItem {
+    Image(Photo),
+    Pdf(Poster),
+}
 
 #[derive(Clone, Debug)]
-pub struct BasePath(pub String);
-
-impl BasePath {
-    pub fn path(&self, path: &str) -> String {
-        let path = path.trim_start_matches('/');
-        if self.0.is_empty() {
-            format!("/{}", path)
-        } else {
-            format!("{}/{}", self.0, path)
-        }
+struct LightboxState(ArcRwSignal<Option<LightboxItem>>);
+
+/// The lightbox's state. Islands hydrate without the app's context, so in an islands build
+/// the ones on a page share a single state instead.
+fn lightbox_state() -> RwSignal<Option<LightboxItem>> {
+    if let Some(LightboxState(state)) = use_context() {
+        return state.into();
     }
+    thread_local! {
+        static SHARED: ArcRwSignal<Option<LightboxItem>> = ArcRwSignal::new(None);
+    }
+    SHARED.with(Clone::clone).into()
 }
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -33,7 +52,7 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <meta charset="utf-8"/>
                 <meta name="viewport" content="width=device-width, initial-scale=1"/>
                 <AutoReload options=options.clone() />
-                <HydrationScripts options/>
+                <HydrationScripts options islands=cfg!(feature = "islands")/>
                 <MetaTags/>
             </head>
             <body>
@@ -46,147 +65,426 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
-    let (selected_image, set_selected_image) = signal(None::<String>);
-    provide_context(LightboxState(set_selected_image));
-
-    // Get base path from the HTML <base> tag or fallback to location
-    let base_path = document()
-        .query_selector("base")
-        .ok()
-        .flatten()
-        .and_then(|base| base.get_attribute("href"))
-        .unwrap_or_else(|| {
-            let path = window().location().pathname().unwrap_or_default();
-            if path.starts_with("/home") {
-                "/home/".to_string()
-            } else {
-                "/".to_string()
-            }
-        });
+    provide_context(LightboxState(ArcRwSignal::new(None)));
 
-    // Leptos Router base should be the prefix without the trailing slash
-    let router_base = if base_path == "/" {
-        "".to_string()
-    } else {
-        base_path.trim_end_matches('/').to_string()
-    };
-    provide_context(BasePath(router_base.clone()));
+    let base = BasePath::configured();
+    let router_base = base.0.clone();
+    provide_context(base);
+    let (is_routing, set_is_routing) = signal(false);
+    let _ = window_event_listener(leptos::ev::mouseover, |ev| prefetch_on_intent(&ev));
+    let _ = window_event_listener(leptos::ev::focusin, |ev| prefetch_on_intent(&ev));
 
     view! {
         <Stylesheet id="leptos" href="pkg/portfolio.css"/>
         <Title text="Ryan Son | Full-Stack Developer"/>
 
-        <Router base=router_base>
+        <Router base=router_base set_is_routing>
+            <div class="routing-progress" aria-hidden="true">
+                <RoutingProgress is_routing max_time=Duration::from_secs(1)/>
+            </div>
             <main>
-                <Routes fallback=NotFoundPage>
+                <Routes fallback=NotFoundPage transition=route_transitions()>
                     <Route path=path!("/") view=HomePage/>
-                    <Route path=path!("/project/:id") view=ProjectLoader/>
-                    <Route path=path!("/blog/:id") view=BlogLoader/>
-                    <Route path=path!("/projects") view=ProjectsPage/>
-                    <Route path=path!("/blog") view=BlogPage/>
-                    <Route path=path!("/photos") view=PhotosPage/>
-                    <Route path=path!("/about") view=AboutPage/>
+                    <Route path=path!("/project/:id") view={Lazy::<ProjectRoute>::new()}/>
+                    <Route path=path!("/blog/:id") view={Lazy::<BlogRoute>::new()}/>
+                    <Route path=path!("/projects") view={Lazy::<ProjectsRoute>::new()}/>
+                    <Route path=path!("/blog") view={Lazy::<BlogsRoute>::new()}/>
+                    <Route path=path!("/photos") view={Lazy::<PhotosRoute>::new()}/>
+                    <Route path=path!("/about") view={Lazy::<AboutRoute>::new()}/>
+                    <Route path=path!("/admin/login") view={Lazy::<AdminLoginRoute>::new()}/>
+                    <ParentRoute path=path!("/admin") view={Lazy::<AdminRoute>::new()}>
+                        <Route path=path!("") view={Lazy::<AdminDashboardRoute>::new()}/>
+                        <Route path=path!("projects") view={Lazy::<ProjectListRoute>::new()}/>
+                        <Route path=path!("projects/:id") view={Lazy::<ProjectEditorRoute>::new()}/>
+                        <Route path=path!("projects/:id/history") view={Lazy::<ProjectHistoryRoute>::new()}/>
+                        <Route path=path!("blogs") view={Lazy::<BlogListRoute>::new()}/>
+                        <Route path=path!("blogs/:id") view={Lazy::<BlogEditorRoute>::new()}/>
+                        <Route path=path!("blogs/:id/history") view={Lazy::<BlogHistoryRoute>::new()}/>
+                        <Route path=path!("photos") view={Lazy::<PhotoEditorRoute>::new()}/>
+                        <Route path=path!("media") view={Lazy::<MediaLibraryRoute>::new()}/>
+                        <Route path=path!("audit") view={Lazy::<AuditLogRoute>::new()}/>
+                    </ParentRoute>
                 </Routes>
             </main>
-            <Lightbox selected_image=selected_image set_selected_image=set_selected_image/>
+            <Lightbox/>
         </Router>
     }
 }
 
-#[component]
-fn Lightbox(
-    selected_image: ReadSignal<Option<String>>,
-    set_selected_image: WriteSignal<Option<String>>
-) -> impl IntoView {
+/// Whether navigation animates, through the View Transitions API: only where the browser
+/// supports it and the visitor does not prefer reduced motion. Server rendering has no
+/// navigation to animate.
+fn route_transitions() -> bool {
+    #[cfg(feature = "ssr")]
+    return false;
+    #[cfg(not(feature = "ssr"))]
+    {
+        let reduced_motion = window()
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .is_some_and(|query| query.matches());
+        let supported = leptos::web_sys::css::supports_with_value("view-transition-name", "none")
+            .unwrap_or(false);
+        supported && !reduced_motion
+    }
+}
+
+/// Styles a card and the header of the page it opens with the same `view-transition-name`,
+/// so that the card grows into the header on navigation. Names must be identifiers, which a
+/// path, or an id starting with a digit, is not on its own.
+fn transition_style(route: &Route) -> String {
+    let name: String = route
+        .path()
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("view-transition-name: {name}")
+}
+
+/// Routes whose page is only loaded on first navigation, so the initial bundle holds little
+/// more than the home page. Each `route, load => view` defines the route type and a module
+/// `load` with the function that builds its view; with the `split` feature that function is
+/// a split point, and `cargo leptos build --split` moves the page's code into its own WASM
+/// chunk, which [`prefetch`] can fetch ahead of navigation.
+macro_rules! lazy_routes {
+    ($($route:ident, $load:ident => $view:expr;)*) => {$(
+        struct $route;
+
+        impl LazyRoute for $route {
+            fn data() -> Self {
+                $route
+            }
+
+            async fn view(_: Self) -> AnyView {
+                $load::view().await
+            }
+
+            async fn preload() {
+                #[cfg(all(feature = "split", any(feature = "csr", feature = "hydrate")))]
+                $load::__preload_view().await;
+            }
+        }
+
+        mod $load {
+            use super::*;
+
+            #[cfg_attr(feature = "split", lazy($load))]
+            pub async fn view() -> AnyView {
+                $view.into_any()
+            }
+        }
+    )*};
+}
+
+lazy_routes! {
+    ProjectRoute, project_page => view! { <ProjectLoader/> };
+    BlogRoute, blog_page => view! { <BlogLoader/> };
+    ProjectsRoute, projects_page => view! { <ProjectsPage/> };
+    BlogsRoute, blogs_page => view! { <BlogPage/> };
+    PhotosRoute, photos_page => view! { <PhotosPage/> };
+    AboutRoute, about_page => view! { <AboutPage/> };
+    AdminLoginRoute, admin_login_page => view! { <AdminLogin/> };
+    AdminRoute, admin_layout => view! { <AdminLayout/> };
+    AdminDashboardRoute, admin_dashboard_page => view! { <AdminDashboard/> };
+    ProjectListRoute, project_list_page => view! { <ProjectList/> };
+    ProjectEditorRoute, project_editor_page => view! { <ProjectEditor/> };
+    ProjectHistoryRoute, project_history_page => view! { <ProjectHistory/> };
+    BlogListRoute, blog_list_page => view! { <BlogList/> };
+    BlogEditorRoute, blog_editor_page => view! { <BlogEditor/> };
+    BlogHistoryRoute, blog_history_page => view! { <BlogHistory/> };
+    PhotoEditorRoute, photo_editor_page => view! { <PhotoEditor/> };
+    MediaLibraryRoute, media_library_page => view! { <MediaLibrary/> };
+    AuditLogRoute, audit_log_page => view! { <AuditLog/> };
+}
+
+/// Fetches what the page at `path` (below the base path) needs before the visitor opens it:
+/// its route's WASM chunk in a split build and, in the CSR build, the content file it shows,
+/// which stays cached for the page's life. Each page is prefetched once.
+fn prefetch(path: &str) {
+    thread_local! {
+        static PREFETCHED: RefCell<HashSet<&'static str>> = RefCell::default();
+    }
+
+    let Some(route) = Route::parse(path) else {
+        return;
+    };
+    if !PREFETCHED.with_borrow_mut(|prefetched| prefetched.insert(route.pattern())) {
+        return;
+    }
+    leptos::task::spawn_local(async move {
+        let csr = cfg!(feature = "csr");
+        match route {
+            Route::Home | Route::Section(_) if csr => {
+                let _ = fetch_projects().await;
+                let _ = fetch_blogs().await;
+                let _ = fetch_photos().await;
+            }
+            Route::Project(_) => {
+                ProjectRoute::preload().await;
+                if csr {
+                    let _ = fetch_projects().await;
+                }
+            }
+            Route::Projects => {
+                ProjectsRoute::preload().await;
+                if csr {
+                    let _ = fetch_projects().await;
+                }
+            }
+            Route::BlogPost(_) => {
+                BlogRoute::preload().await;
+                if csr {
+                    let _ = fetch_blogs().await;
+                }
+            }
+            Route::Blog => {
+                BlogsRoute::preload().await;
+                if csr {
+                    let _ = fetch_blogs().await;
+                }
+            }
+            Route::Photos => {
+                PhotosRoute::preload().await;
+                if csr {
+                    let _ = fetch_photos().await;
+                }
+            }
+            Route::About => AboutRoute::preload().await,
+            _ => {}
+        }
+    });
+}
+
+/// Prefetches the `data-prefetch` path of a link as the pointer or focus reaches it.
+fn prefetch_on_intent(ev: &leptos::web_sys::Event) {
+    use leptos::wasm_bindgen::JsCast;
+
+    let link = ev
+        .target()
+        .and_then(|target| target.dyn_into::<leptos::web_sys::Element>().ok())
+        .and_then(|target| target.closest("[data-prefetch]").ok().flatten());
+    if let Some(path) = link.and_then(|link| link.get_attribute("data-prefetch")) {
+        prefetch(&path);
+    }
+}
+
+/// Directive prefetching an element's `data-prefetch` path once it scrolls into view. All
+/// elements share one observer.
+fn prefetch_in_view(el: leptos::web_sys::Element) {
+    use leptos::wasm_bindgen::{closure::Closure, JsCast};
+    use leptos::web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+    thread_local! {
+        static OBSERVER: Option<IntersectionObserver> = {
+            let callback = Closure::<dyn Fn(Vec<IntersectionObserverEntry>, IntersectionObserver)>::new(
+                |entries: Vec<IntersectionObserverEntry>, observer: IntersectionObserver| {
+                    for entry in entries.iter().filter(|entry| entry.is_intersecting()) {
+                        let target = entry.target();
+                        observer.unobserve(&target);
+                        if let Some(path) = target.get_attribute("data-prefetch") {
+                            prefetch(&path);
+                        }
+                    }
+                },
+            );
+            let observer = IntersectionObserver::new(callback.as_ref().unchecked_ref()).ok();
+            // Lives as long as the page, like the observer.
+            callback.forget();
+            observer
+        };
+    }
+
+    OBSERVER.with(|observer| {
+        if let Some(observer) = observer {
+            observer.observe(&el);
+        }
+    });
+}
+
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+fn Lightbox() -> impl IntoView {
+    let selected = lightbox_state();
+    let close = move || selected.set(None);
+    let _ = window_event_listener(leptos::ev::keydown, move |ev| {
+        if ev.key() == "Escape" && selected.with_untracked(Option::is_some) {
+            close();
+        }
+    });
+
     view! {
-        {move || selected_image.get().map(|url| view! {
-            <div class="lightbox-overlay" on:click=move |_| set_selected_image.set(None)>
-                <div class="lightbox-content" on:click=move |ev| ev.stop_propagation()>
-                    <img src=url alt="Full size photo"/>
-                    <button class="lightbox-close" on:click=move |_| set_selected_image.set(None)>"×"</button>
+        {move || selected.get().map(|item| {
+            let content = match item {
+                LightboxItem::Image(photo) => view! { <img src=photo.url.clone() alt=photo.alt_text()/> }.into_any(),
+                LightboxItem::Pdf(poster) => view! { <PdfViewer poster/> }.into_any(),
+            };
+            view! {
+                <div class="lightbox-overlay" on:click=move |_| close()>
+                    <div class="lightbox-content" on:click=move |ev| ev.stop_propagation()>
+                        {content}
+                        <button class="lightbox-close" aria-label="Close" on:click=move |_| close()>"×"</button>
+                    </div>
                 </div>
-            </div>
+            }
         })}
     }
 }
 
+/// A PDF shown inline in the lightbox, with the actions of a regular link to it.
+#[component]
+fn PdfViewer(poster: Poster) -> impl IntoView {
+    let Poster {
+        name, url, pages, ..
+    } = poster;
+    let (download, open, fallback) = (url.clone(), url.clone(), url.clone());
+    view! {
+        <div class="lightbox-viewer">
+            <div class="lightbox-toolbar">
+                <span class="lightbox-title">{name.clone()}</span>
+                {pages.map(|pages| view! { <span class="lightbox-pages">{page_count(pages)}</span> })}
+                <a class="btn btn-secondary" href=download download>"Download"</a>
+                <a class="btn btn-secondary" href=open target="_blank" rel="noopener">"Open in New Tab"</a>
+            </div>
+            <object class="lightbox-pdf" data=url type="application/pdf" aria-label=name>
+                <p class="lightbox-fallback">
+                    "This browser cannot show the PDF here. "
+                    <a href=fallback target="_blank" rel="noopener">"Open it in a new tab"</a>
+                    " instead."
+                </p>
+            </object>
+        </div>
+    }
+}
+
+fn page_count(pages: u32) -> String {
+    if pages == 1 {
+        "1 page".to_string()
+    } else {
+        format!("{pages} pages")
+    }
+}
+
 #[component]
 pub fn ProjectLoader() -> impl IntoView {
     let params = use_params_map();
-
-    let project_data = move || {
-        let id = params.get().get("id").unwrap_or_default();
-        get_project_by_id(&id)
-    };
+    let query = use_query_map();
+
+    let project_data = Resource::new(
+        move || {
+            let query = query.get();
+            (
+                params.get().get("id").unwrap_or_default(),
+                query.get("preview"),
+                query.get("invite"),
+            )
+        },
+        |(id, preview, invite)| fetch_project(id, preview, invite),
+    );
 
     let base = use_context::<BasePath>().expect("BasePath context not found");
 
     view! {
-        {move || match project_data() {
-            Some(data) => view! { <ProjectDetail project=data/> }.into_any(),
-            None => view! {
-                <div>
-                    <Navbar/>
-                    <div class="container" style="padding-top: 100px; margin-bottom: 30px; text-align: center;">
-                        <h1>"Project Not Found"</h1>
-                        <p>"The project you are looking for does not exist."</p>
-                        <A href=base.path("/") attr:style="margin-top: 20px" attr:class="btn btn-primary">"Return Home"</A>
-                    </div>
-                    <Footer/>
-                </div>
-            }.into_any(),
-        }}
+        <Suspense fallback=|| view! { <DetailSkeleton/> }>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    match project_data.await {
+                        Ok(Some(ProjectPage::Full(data))) => view! { <ProjectDetail project=data/> }.into_any(),
+                        Ok(Some(ProjectPage::Locked(teaser))) => view! {
+                            <LockedProject project=teaser on_unlock=move || project_data.refetch()/>
+                        }.into_any(),
+                        Err(_) => view! {
+                            <div>
+                                <Navbar/>
+                                <div class="container" style="padding-top: 100px; margin-bottom: 30px;">
+                                    <LoadError what="this project" retry=move || project_data.refetch()/>
+                                </div>
+                                <Footer/>
+                            </div>
+                        }.into_any(),
+                        Ok(None) => view! {
+                            <div>
+                                <Navbar/>
+                                <div class="container" style="padding-top: 100px; margin-bottom: 30px; text-align: center;">
+                                    <h1>"Project Not Found"</h1>
+                                    <p>"The project you are looking for does not exist."</p>
+                                    <A href=Route::Home.href(&base) attr:style="margin-top: 20px" attr:class="btn btn-primary">"Return Home"</A>
+                                </div>
+                                <Footer/>
+                            </div>
+                        }.into_any(),
+                    }
+                })
+            }}
+        </Suspense>
     }
 }
 
 #[component]
-fn ProjectDetail(project: Project) -> impl IntoView {
+pub fn ProjectDetail(project: Project) -> impl IntoView {
     let base = use_context::<BasePath>().expect("BasePath context not found");
     view! {
         <div>
             <Navbar/>
             <section class="project-detail">
                 <div class="container">
-                    <A href=base.path("/#projects") attr:class="back-link">"← Back to Portfolio"</A>
+                    <A href=Route::Section(Section::Projects).href(&base) attr:class="back-link">"← Back to Portfolio"</A>
 
-                    <div class="project-header">
+                    <StatusNotice status=project.status/>
+                    <div class="project-header" style=transition_style(&Route::Project(project.id.clone()))>
                         <span class="tag">{project.tag}</span>
                         <h1>{project.title}</h1>
                         <p class="project-subtitle">{project.subtitle}</p>
                     </div>
 
                     <div class="project-content">
-                        <div class="project-section">
-                            <h2>"Overview"</h2>
-                            <p>{project.overview}</p>
-                        </div>
+                        {(!project.overview.is_empty()).then(|| view! {
+                            <div class="project-section">
+                                <h2>"Overview"</h2>
+                                <p>{project.overview.clone()}</p>
+                            </div>
+                        })}
 
-                        <div class="project-section">
-                            <h2>"My Role"</h2>
-                            <p>{project.role}</p>
-                        </div>
+                        {(!project.role.is_empty()).then(|| view! {
+                            <div class="project-section">
+                                <h2>"My Role"</h2>
+                                <p>{project.role.clone()}</p>
+                            </div>
+                        })}
 
-                        <div class="project-section">
-                            <h2>"Technologies"</h2>
-                            <div class="tech-tags">
-                                <For
-                                    each=move || project.technologies.clone().unwrap_or_else(Vec::new)
-                                    key=|tech| tech.to_string()
-                                    children=|tech| view! { <span class="tech-tag">{tech}</span> }
-                                />
+                        {project.technologies.clone().filter(|technologies| !technologies.is_empty()).map(|technologies| view! {
+                            <div class="project-section">
+                                <h2>"Technologies"</h2>
+                                <div class="tech-tags">
+                                    <For
+                                        each=move || technologies.to_vec()
+                                        key=|tech| tech.clone()
+                                        children=|tech| view! { <span class="tech-tag">{tech}</span> }
+                                    />
+                                </div>
                             </div>
-                        </div>
+                        })}
+
+                        {project.blocks.clone().map(|blocks| view! { <Blocks blocks/> })}
+
+                        {project.demo.filter(|demo| demos::find(demo).is_some()).map(|demo| view! { <DemoSection demo/> })}
 
                         {move || project.posters.clone().map(|posters| view! {
                              <div class="project-section">
                                 <h2>"Posters"</h2>
                                 <div class="posters-grid">
                                     <For
-                                        each=move || posters.clone()
-                                        key=|poster| poster.name
-                                        children=|poster| view! {
-                                            <a href=poster.url target="_blank" class="btn btn-secondary">{poster.name}</a>
-                                        }
+                                        each=move || posters.to_vec()
+                                        key=|poster| poster.name.clone()
+                                        children=|poster| view! { <PosterCard poster/> }
                                     />
                                 </div>
                             </div>
@@ -195,34 +493,35 @@ fn ProjectDetail(project: Project) -> impl IntoView {
                         <div class="project-section">
                             <h2>"Resources"</h2>
                             {
-                                let photo_urls = project.photos.clone().map(|photos| {
-                                    photos
-                                        .iter()
-                                        .map(|p| p.url.to_string())
-                                        .collect::<Vec<String>>()
-                                });
+                                let photos = project.photos.as_deref().map(<[Photo]>::to_vec);
 
                                 view! {
                                     <div class="project-section">
-                                        {if let Some(urls) = photo_urls {
-                                            view! { <Slideshow images=urls/> }
+                                        {if let Some(photos) = photos {
+                                            view! { <Slideshow images=photos/> }
                                                 .into_any()
                                         } else {
-                                            view! {}
-                                                .into_any()
+                                            ().into_any()
                                         }}
                                     </div>
                                 }
                             }
 
                             <div class="project-links">
-                                {move || project.paper_link.map(|link| view! {
-                                    <a href=link target="_blank" class="btn btn-primary">"Read Paper"</a>
+                                {move || project.paper_link.clone().map(|link| view! {
+                                    <a
+                                        href=link
+                                        title=project.paper_source.as_ref().map(|source| format!("Copy of {source}"))
+                                        target="_blank"
+                                        class="btn btn-primary"
+                                    >
+                                        "Read Paper"
+                                    </a>
                                 })}
-                                {move || project.code_link.map(|link| view! {
+                                {move || project.code_link.clone().map(|link| view! {
                                     <a href=link target="_blank" class="btn btn-secondary">"View Code"</a>
                                 })}
-                                {move || project.live_link.map(|link| view! {
+                                {move || project.live_link.clone().map(|link| view! {
                                     <a href=link target="_blank" class="btn btn-secondary">"Live Site"</a>
                                 })}
                             </div>
@@ -235,12 +534,140 @@ fn ProjectDetail(project: Project) -> impl IntoView {
     }
 }
 
+/// Reloads a private project once it is unlocked.
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "csr", allow(dead_code))]
+struct OnUnlock(Callback<()>);
+
+/// The teaser of a private project, with a form to unlock the rest by passphrase.
+#[component]
+fn LockedProject(project: Project, #[prop(into)] on_unlock: Callback<()>) -> impl IntoView {
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    provide_context(OnUnlock(on_unlock));
+    view! {
+        <div>
+            <Navbar/>
+            <section class="project-detail">
+                <div class="container">
+                    <A href=Route::Section(Section::Projects).href(&base) attr:class="back-link">"← Back to Portfolio"</A>
+
+                    <div class="project-header" style=transition_style(&Route::Project(project.id.clone()))>
+                        <span class="tag">{project.tag}</span>
+                        <h1>{project.title}</h1>
+                        <p class="project-subtitle">{project.subtitle}</p>
+                    </div>
+
+                    <div class="project-content">
+                        <div class="project-section">
+                            <p>{project.description}</p>
+                        </div>
+                        <div class="project-section project-locked">
+                            <h2>"Private Case Study"</h2>
+                            <UnlockForm id=project.id/>
+                        </div>
+                    </div>
+                </div>
+            </section>
+            <Footer/>
+        </div>
+    }
+}
+
+/// As an island the form has no [`OnUnlock`] to call, so it reloads the page instead.
+#[cfg(not(feature = "csr"))]
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+fn UnlockForm(id: Text) -> impl IntoView {
+    use crate::content::UnlockProject;
+
+    let unlock = ServerAction::<UnlockProject>::new();
+    let passphrase = RwSignal::new(String::new());
+    let on_unlock = use_context::<OnUnlock>();
+    Effect::new(move |_| {
+        if let Some(Ok(())) = unlock.value().get() {
+            match on_unlock {
+                Some(OnUnlock(on_unlock)) => on_unlock.run(()),
+                None => {
+                    let _ = window().location().reload();
+                }
+            }
+        }
+    });
+    let error = move || {
+        unlock
+            .value()
+            .get()
+            .and_then(Result::err)
+            .map(|err| match err {
+                ServerFnError::ServerError(msg) => msg,
+                other => other.to_string(),
+            })
+    };
+
+    view! {
+        <p>"The details of this project are shared on request. Enter the passphrase you were given to read on."</p>
+        <form class="unlock-form" on:submit=move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            unlock.dispatch(UnlockProject {
+                id: id.to_string(),
+                passphrase: passphrase.get_untracked(),
+            });
+        }>
+            <input
+                type="password"
+                aria-label="Passphrase"
+                placeholder="Passphrase"
+                autocomplete="off"
+                required
+                prop:value=move || passphrase.get()
+                on:input=move |ev| passphrase.set(event_target_value(&ev))
+            />
+            <button type="submit" class="btn btn-primary" disabled=move || unlock.pending().get()>"Unlock"</button>
+        </form>
+        {move || error().map(|msg| view! { <p class="unlock-error">{msg}</p> })}
+    }
+}
+
+/// The static build has no server to check a passphrase against.
+#[cfg(feature = "csr")]
+#[component]
+fn UnlockForm(id: Text) -> impl IntoView {
+    let _ = id;
+    view! { <p>"The details of this project are shared on request. Get in touch for access."</p> }
+}
+
+/// Tells the reader when a page is not (or no longer) published, e.g. when opened
+/// through a preview link.
+#[component]
+fn StatusNotice(status: Status) -> impl IntoView {
+    let message = match status {
+        Status::Published => return ().into_any(),
+        Status::Draft => "Draft preview: this page is not published.".to_string(),
+        Status::Scheduled { publish_at } => {
+            format!(
+                "Scheduled preview: this page goes live on {} UTC.",
+                publish_at.format("%B %-d, %Y at %H:%M")
+            )
+        }
+        Status::Archived => "This page is archived and may be out of date.".to_string(),
+    };
+    view! { <p class="status-notice">{message}</p> }.into_any()
+}
+
+/// The home page section in view, for the navbar to highlight.
+#[derive(Clone, Copy)]
+struct SectionInView(ReadSignal<Option<Section>>);
+
 #[component]
 fn HomePage() -> impl IntoView {
     let location = use_location();
+    // The one in the URL hash at first, then what is scrolled to.
+    let (section, set_section) = signal(None::<Section>);
+    provide_context(SectionInView(section));
 
     Effect::new(move |_| {
         let hash = location.hash.get();
+        set_section.set(Section::from_id(hash.trim_start_matches('#')));
 
         if !hash.is_empty() {
             let hash_string = hash.to_string();
@@ -253,6 +680,16 @@ fn HomePage() -> impl IntoView {
             });
         }
     });
+    // Effects run once the sections are mounted. The page is mounted again on every visit,
+    // so the spy starts however the home page is reached and stops when it is left. In an
+    // islands build neither this page nor the navbar is hydrated, so there is no scroll spy:
+    // the navbar highlights only what the server rendered and the hash does not follow.
+    Effect::new(move |_| {
+        scroll_spy(move |in_view| {
+            set_section.set(Some(in_view));
+            replace_hash(in_view);
+        });
+    });
 
     view! {
         <Navbar/>
@@ -267,77 +704,161 @@ fn HomePage() -> impl IntoView {
 #[component]
 fn Navbar() -> impl IntoView {
     let location = use_location();
-    let (current_hash, set_current_hash) = signal(String::new());
-    let (is_open, set_is_open) = signal(false);
-
-    Effect::new(move |_| {
-        set_current_hash.set(location.hash.get());
-    });
-
-    let scroll_to = move |target_id: &str| {
-        set_is_open.set(false);
-        if location.pathname.get() == "/" {
-            let target_id_owned = target_id.to_string();
+    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let routes = StoredValue::new(base.clone());
+    let current = move || {
+        routes.with_value(|routes| {
+            routes
+                .route(&location.pathname.get())
+                .and_then(Route::parse)
+        })
+    };
+    let on_home = move || current() == Some(Route::Home);
+    let section = use_context::<SectionInView>().map(|SectionInView(section)| section);
 
+    let scroll_to = move |target: Section| {
+        if on_home() {
             request_animation_frame(move || {
-                if let Some(el) = document().get_element_by_id(&target_id_owned) {
+                if let Some(el) = document().get_element_by_id(target.id()) {
                     el.scroll_into_view();
                 }
             });
         }
     };
 
-    let is_active = move |path: &str| {
-        let current_path = location.pathname.get();
-        if path == "/" { current_path == "/" } else { current_path.starts_with(path) }
-    };
-
-    let base = use_context::<BasePath>().expect("BasePath context not found");
+    let in_view =
+        move |target: Section| section.is_some_and(|section| section.get() == Some(target));
+    let link = move |route: Route| route.href(&routes.read_value());
 
     view! {
-        <nav>
+        <NavMenu>
             <div class="logo">
-                <A href=base.path("/") on:click=move |_| set_is_open.set(false)>"Ryan Son"</A>
+                <A href=link(Route::Home) attr:data-prefetch=Route::Home.path()>"Ryan Son"</A>
             </div>
 
-            <button
-                class="menu-toggle"
-                class:open=move || is_open.get()
-                on:click=move |_| set_is_open.update(|n| *n = !*n)
-                aria-label="Toggle menu"
-            >
+            <button class="menu-toggle" aria-label="Toggle menu">
                 <span></span>
                 <span></span>
                 <span></span>
             </button>
 
-            <ul class="nav-links" class:open=move || is_open.get()>
+            <ul class="nav-links">
                 <li>
-                    <A href=base.path("/")
-                       class:active=move || is_active("/")
-                       on:click=move |_| scroll_to("home")>"Home"</A>
+                    <A href=link(Route::Home)
+                       attr:data-prefetch=Route::Home.path()
+                       class:active=move || on_home() && section.is_none_or(|section| matches!(section.get(), None | Some(Section::Home)))
+                       on:click=move |_| scroll_to(Section::Home)>"Home"</A>
                 </li>
                 <li>
-                    <A href=base.path("/projects")
-                       class:active=move || is_active("/projects") || is_active("/project") || current_hash.get() == "#projects"
-                       on:click=move |_| scroll_to("projects")>"Projects"</A>
+                    <A href=link(Route::Projects)
+                       attr:data-prefetch=Route::Projects.path()
+                       class:active=move || matches!(current(), Some(Route::Projects | Route::Project(_))) || in_view(Section::Projects)
+                       on:click=move |_| scroll_to(Section::Projects)>"Projects"</A>
                 </li>
                 <li>
-                    <A href=base.path("/blog")
-                       class:active=move || is_active("/blog") || current_hash.get() == "#blogs"
-                       on:click=move |_| scroll_to("blogs")>"Blogs"</A>
+                    <A href=link(Route::Blog)
+                       attr:data-prefetch=Route::Blog.path()
+                       class:active=move || matches!(current(), Some(Route::Blog | Route::BlogPost(_))) || in_view(Section::Blogs)
+                       on:click=move |_| scroll_to(Section::Blogs)>"Blogs"</A>
                 </li>
                 <li>
-                    <A href=base.path("/photos")
-                       class:active=move || is_active("/photos") || current_hash.get() == "#photos"
-                       on:click=move |_| scroll_to("photos")>"Photos"</A>
+                    <A href=link(Route::Photos)
+                       attr:data-prefetch=Route::Photos.path()
+                       class:active=move || current() == Some(Route::Photos) || in_view(Section::Photos)
+                       on:click=move |_| scroll_to(Section::Photos)>"Photos"</A>
                 </li>
                 <li>
-                    <A href=base.path("/about")
-                       class:active=move || is_active("/about")
-                       on:click=move |_| set_is_open.set(false)>"About"</A>
+                    <A href=link(Route::About)
+                       attr:data-prefetch=Route::About.path()
+                       class:active=move || current() == Some(Route::About)>"About"</A>
                 </li>
             </ul>
+        </NavMenu>
+    }
+}
+
+/// Calls `on_section` with whichever home page section crosses the middle of the viewport,
+/// each time that changes, until the calling scope is cleaned up.
+fn scroll_spy(on_section: impl Fn(Section) + 'static) {
+    use leptos::wasm_bindgen::{closure::Closure, JsCast};
+    use leptos::web_sys::{
+        IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit,
+    };
+
+    let callback = Closure::<dyn Fn(Vec<IntersectionObserverEntry>)>::new(
+        move |entries: Vec<IntersectionObserverEntry>| {
+            let in_view = entries
+                .iter()
+                .rev()
+                .filter(|entry| entry.is_intersecting())
+                .find_map(|entry| Section::from_id(&entry.target().id()));
+            if let Some(section) = in_view {
+                on_section(section);
+            }
+        },
+    );
+    let options = IntersectionObserverInit::new();
+    options.set_root_margin("-50% 0px -50% 0px");
+    let Ok(observer) =
+        IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options)
+    else {
+        return;
+    };
+    for element in Section::ALL
+        .into_iter()
+        .filter_map(|section| document().get_element_by_id(section.id()))
+    {
+        observer.observe(&element);
+    }
+
+    let spy = StoredValue::new_local((observer, callback));
+    on_cleanup(move || spy.with_value(|(observer, _)| observer.disconnect()));
+}
+
+/// Points the URL at `section` without adding a history entry; the top section gets no
+/// hash at all.
+fn replace_hash(section: Section) {
+    let Ok(history) = window().history() else {
+        return;
+    };
+    let location = window().location();
+    let mut url = format!(
+        "{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default()
+    );
+    if section != Section::Home {
+        url = format!("{url}#{}", section.id());
+    }
+    let _ = history.replace_state_with_url(&history.state().unwrap_or_default(), "", Some(&url));
+}
+
+/// The navbar itself, opening and closing the menu on small screens. Clicks are handled for
+/// the whole bar, so its links stay plain server-rendered HTML in an islands build: the
+/// toggle button flips the menu and following any link closes it.
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+fn NavMenu(children: Children) -> impl IntoView {
+    use leptos::wasm_bindgen::JsCast;
+
+    let (is_open, set_is_open) = signal(false);
+    let on_click = move |ev: leptos::ev::MouseEvent| {
+        let Some(target) = ev
+            .target()
+            .and_then(|t| t.dyn_into::<leptos::web_sys::Element>().ok())
+        else {
+            return;
+        };
+        if target.closest(".menu-toggle").ok().flatten().is_some() {
+            set_is_open.update(|open| *open = !*open);
+        } else if target.closest("a").ok().flatten().is_some() {
+            set_is_open.set(false);
+        }
+    };
+
+    view! {
+        <nav class:menu-open=move || is_open.get() on:click=on_click>
+            {children()}
         </nav>
     }
 }
@@ -352,7 +873,7 @@ fn Hero() -> impl IntoView {
                 <h1>"From frontend to backend—designed to scale"</h1>
                 <p>"Full-stack developer crafting high-performance applications."</p>
                 <div class="hero-links">
-                    <A href=base.path("/#projects") attr:class="btn btn-primary hero-project-btn">"View My Work"</A>
+                    <A href=Route::Section(Section::Projects).href(&base) attr:class="btn btn-primary hero-project-btn">"View My Work"</A>
                     <a href="https://github.com/rawleo" class="btn btn-secondary" target="_blank">"GitHub"</a>
                     <a href="https://www.linkedin.com/in/ryanson50" class="btn btn-secondary" target="_blank">"LinkedIn"</a>
                     <a href="mailto:sonryan50@gmail.com" class="btn btn-secondary">"Email"</a>
@@ -364,27 +885,35 @@ fn Hero() -> impl IntoView {
 
 #[component]
 fn Projects() -> impl IntoView {
-    let projects = get_projects();
+    let projects = Resource::new(|| (), |_| fetch_projects());
     let base = use_context::<BasePath>().expect("BasePath context not found");
 
     view! {
         <section class="projects container" id="projects">
             <h2 class="section-title">"Featured Projects"</h2>
             <div class="projects-grid">
-                {projects.into_iter().take(3).map(|project| {
-                    view! {
-                        <Card
-                            id=project.id
-                            title=project.title
-                            description=project.description
-                            tag=project.tag
-                            base_path="project"
-                        />
-                    }
-                }).collect::<Vec<_>>()}
+                <Suspense fallback=|| view! { <CardSkeletons count=3/> }>
+                    {move || Suspend::new(async move {
+                        match projects.await {
+                            Ok(list) => list.into_iter().take(3).map(|project| {
+                                let private = project.is_private();
+                                view! {
+                                    <Card
+                                        private
+                                        route=Route::Project(project.id)
+                                        title=project.title
+                                        description=project.description
+                                        tag=project.tag
+                                    />
+                                }
+                            }).collect_view().into_any(),
+                            Err(_) => view! { <LoadError what="projects" retry=move || projects.refetch()/> }.into_any(),
+                        }
+                    })}
+                </Suspense>
             </div>
             <div style="text-align: center; margin-top: 3rem;">
-                <A href=base.path("/projects") attr:class="btn btn-secondary">"View All Projects"</A>
+                <A href=Route::Projects.href(&base) attr:class="btn btn-secondary">"View All Projects"</A>
             </div>
         </section>
     }
@@ -392,27 +921,93 @@ fn Projects() -> impl IntoView {
 
 #[component]
 fn Card(
-    id: &'static str,
-    title: &'static str,
-    description: &'static str,
-    tag: &'static str,
-    base_path: &'static str,
+    route: Route,
+    #[prop(into)] title: Text,
+    #[prop(into)] description: Text,
+    #[prop(into)] tag: Text,
+    /// Marks the card of a private project, which only opens to a teaser.
+    #[prop(optional)]
+    private: bool,
 ) -> impl IntoView {
     let base = use_context::<BasePath>().expect("BasePath context not found");
-    let link = base.path(&format!("{}/{}", base_path, id));
 
     view! {
-        <A href=link attr:class="project-card">
+        <A
+            href=route.href(&base)
+            attr:class="project-card"
+            attr:style=transition_style(&route)
+            attr:data-prefetch=route.path()
+            use:prefetch_in_view
+        >
             <span class="tag">{tag}</span>
+            {private.then(|| view! { <span class="card-lock">"Private"</span> })}
             <h3>{title}</h3>
             <p>{description}</p>
         </A>
     }
 }
 
+/// Placeholder cards shown while a listing loads.
+#[component]
+fn CardSkeletons(count: usize) -> impl IntoView {
+    (0..count)
+        .map(|_| {
+            view! {
+                <div class="project-card skeleton" aria-hidden="true">
+                    <span class="skeleton-line skeleton-tag"></span>
+                    <span class="skeleton-line skeleton-title"></span>
+                    <span class="skeleton-line"></span>
+                    <span class="skeleton-line skeleton-short"></span>
+                </div>
+            }
+        })
+        .collect_view()
+}
+
+#[component]
+fn PhotoSkeletons(count: usize) -> impl IntoView {
+    (0..count)
+        .map(|_| view! {
+            <div class="photo-card skeleton" aria-hidden="true">
+                <div class="skeleton-image"></div>
+                <div class="photo-caption"><span class="skeleton-line skeleton-short"></span></div>
+            </div>
+        })
+        .collect_view()
+}
+
+#[component]
+fn DetailSkeleton() -> impl IntoView {
+    view! {
+        <div>
+            <Navbar/>
+            <section class="project-detail">
+                <div class="container skeleton" aria-busy="true">
+                    <span class="skeleton-line skeleton-tag"></span>
+                    <span class="skeleton-line skeleton-heading"></span>
+                    <span class="skeleton-line skeleton-short"></span>
+                    <span class="skeleton-line"></span>
+                    <span class="skeleton-line"></span>
+                </div>
+            </section>
+        </div>
+    }
+}
+
+/// Shown in place of content that failed to load, with a way to try again.
+#[component]
+fn LoadError(what: &'static str, #[prop(into)] retry: Callback<()>) -> impl IntoView {
+    view! {
+        <div class="load-error" role="alert">
+            <p>{format!("Couldn't load {what}. Check your connection and try again.")}</p>
+            <button class="btn btn-secondary" on:click=move |_| retry.run(())>"Try Again"</button>
+        </div>
+    }
+}
+
 #[component]
 pub fn ProjectsPage() -> impl IntoView {
-    let projects = get_projects();
+    let projects = Resource::new(|| (), |_| fetch_projects());
 
     view! {
         <div>
@@ -420,17 +1015,25 @@ pub fn ProjectsPage() -> impl IntoView {
             <section class="projects container" style="padding-top: 120px;">
                 <h1 class="section-title">"All Projects"</h1>
                 <div class="projects-grid">
-                    {projects.into_iter().map(|project| {
-                        view! {
-                            <Card
-                                id=project.id
-                                title=project.title
-                                description=project.description
-                                tag=project.tag
-                                base_path="project"
-                            />
-                        }
-                    }).collect::<Vec<_>>()}
+                    <Suspense fallback=|| view! { <CardSkeletons count=6/> }>
+                        {move || Suspend::new(async move {
+                            match projects.await {
+                                Ok(list) => list.into_iter().map(|project| {
+                                    let private = project.is_private();
+                                    view! {
+                                        <Card
+                                            private
+                                            route=Route::Project(project.id)
+                                            title=project.title
+                                            description=project.description
+                                            tag=project.tag
+                                        />
+                                    }
+                                }).collect_view().into_any(),
+                                Err(_) => view! { <LoadError what="projects" retry=move || projects.refetch()/> }.into_any(),
+                            }
+                        })}
+                    </Suspense>
                 </div>
             </section>
             <Footer/>
@@ -440,7 +1043,7 @@ pub fn ProjectsPage() -> impl IntoView {
 
 #[component]
 pub fn BlogPage() -> impl IntoView {
-    let blogs = get_blogs();
+    let blogs = Resource::new(|| (), |_| fetch_blogs());
 
     view! {
         <div>
@@ -448,17 +1051,23 @@ pub fn BlogPage() -> impl IntoView {
             <section class="projects container" style="padding-top: 120px;">
                 <h1 class="section-title">"Blog"</h1>
                 <div class="projects-grid">
-                    {blogs.into_iter().map(|blog| {
-                        view! {
-                            <Card
-                                id=blog.id
-                                title=blog.title
-                                description=blog.description
-                                tag=blog.tag
-                                base_path="blog"
-                            />
-                        }
-                    }).collect::<Vec<_>>()}
+                    <Suspense fallback=|| view! { <CardSkeletons count=6/> }>
+                        {move || Suspend::new(async move {
+                            match blogs.await {
+                                Ok(list) => list.into_iter().map(|blog| {
+                                    view! {
+                                        <Card
+                                            route=Route::BlogPost(blog.id)
+                                            title=blog.title
+                                            description=blog.description
+                                            tag=blog.tag
+                                        />
+                                    }
+                                }).collect_view().into_any(),
+                                Err(_) => view! { <LoadError what="blog posts" retry=move || blogs.refetch()/> }.into_any(),
+                            }
+                        })}
+                    </Suspense>
                 </div>
             </section>
             <Footer/>
@@ -466,14 +1075,54 @@ pub fn BlogPage() -> impl IntoView {
     }
 }
 
-#[component]
-fn PhotoCard(photo: Photo) -> impl IntoView {
-    let setter = use_context::<LightboxState>().expect("LightboxState context not found").0;
-    let url = photo.url.to_string();
+/// A poster's thumbnail, opening the PDF in the lightbox. Without scripts it is a plain
+/// link to the PDF.
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+fn PosterCard(poster: Poster) -> impl IntoView {
+    let setter = lightbox_state();
+    let thumbnail = match &poster.preview {
+        Some(preview) => {
+            view! { <img src=preview.clone() alt=format!("Preview of {}", poster.name) loading="lazy"/> }.into_any()
+        }
+        None => view! { <span class="poster-placeholder">"PDF"</span> }.into_any(),
+    };
+    let title = poster
+        .source
+        .as_ref()
+        .map(|source| format!("Copy of {source}"));
+    let (name, url, pages) = (poster.name.clone(), poster.url.clone(), poster.pages);
+    let item = LightboxItem::Pdf(poster);
 
     view! {
-        <div class="photo-card" on:click=move |_| setter.set(Some(url.clone()))>
-            <img src=photo.url alt=photo.caption/>
+        <a
+            class="poster-card"
+            href=url
+            title=title
+            target="_blank"
+            on:click=move |ev| {
+                ev.prevent_default();
+                setter.set(Some(item.clone()));
+            }
+        >
+            <div class="poster-thumb">{thumbnail}</div>
+            <div class="poster-info">
+                <span class="poster-name">{name}</span>
+                {pages.map(|pages| view! { <span class="poster-pages">{page_count(pages)}</span> })}
+            </div>
+        </a>
+    }
+}
+
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+pub fn PhotoCard(photo: Photo) -> impl IntoView {
+    let setter = lightbox_state();
+    let item = LightboxItem::Image(photo.clone());
+
+    view! {
+        <div class="photo-card" on:click=move |_| setter.set(Some(item.clone()))>
+            <img src=photo.url.clone() alt=photo.alt_text()/>
             <div class="photo-caption">{photo.caption}</div>
         </div>
     }
@@ -481,7 +1130,7 @@ fn PhotoCard(photo: Photo) -> impl IntoView {
 
 #[component]
 pub fn PhotosPage() -> impl IntoView {
-    let photos = get_photos();
+    let photos = Resource::new(|| (), |_| fetch_photos());
 
     view! {
         <div>
@@ -489,11 +1138,18 @@ pub fn PhotosPage() -> impl IntoView {
             <section class="photos container" style="padding-top: 120px;">
                 <h1 class="section-title">"Photos"</h1>
                 <div class="photos-grid">
-                    {photos.into_iter().map(|photo| {
-                        view! {
-                            <PhotoCard photo=photo />
-                        }
-                    }).collect::<Vec<_>>()}
+                    <Suspense fallback=|| view! { <PhotoSkeletons count=6/> }>
+                        {move || Suspend::new(async move {
+                            match photos.await {
+                                Ok(list) => list.into_iter().map(|photo| {
+                                    view! {
+                                        <PhotoCard photo=photo />
+                                    }
+                                }).collect_view().into_any(),
+                                Err(_) => view! { <LoadError what="photos" retry=move || photos.refetch()/> }.into_any(),
+                            }
+                        })}
+                    </Suspense>
                 </div>
             </section>
             <Footer/>
@@ -503,74 +1159,101 @@ pub fn PhotosPage() -> impl IntoView {
 
 #[component]
 fn Blogs() -> impl IntoView {
-    let blogs = get_blogs();
+    let blogs = Resource::new(|| (), |_| fetch_blogs());
     let base = use_context::<BasePath>().expect("BasePath context not found");
 
     view! {
         <section class="projects container" id="blogs">
             <h2 class="section-title">"Featured Blogs"</h2>
             <div class="projects-grid">
-                {blogs.into_iter().take(3).map(|blog| {
-                    view! {
-                        <Card
-                            id=blog.id
-                            title=blog.title
-                            description=blog.description
-                            tag=blog.tag
-                            base_path="blog"
-                        />
-                    }
-                }).collect::<Vec<_>>()}
+                <Suspense fallback=|| view! { <CardSkeletons count=3/> }>
+                    {move || Suspend::new(async move {
+                        match blogs.await {
+                            Ok(list) => list.into_iter().take(3).map(|blog| {
+                                view! {
+                                    <Card
+                                        route=Route::BlogPost(blog.id)
+                                        title=blog.title
+                                        description=blog.description
+                                        tag=blog.tag
+                                    />
+                                }
+                            }).collect_view().into_any(),
+                            Err(_) => view! { <LoadError what="blog posts" retry=move || blogs.refetch()/> }.into_any(),
+                        }
+                    })}
+                </Suspense>
             </div>
             <div style="text-align: center; margin-top: 3rem;">
-                <A href=base.path("/blog") attr:class="btn btn-secondary">"View All Posts"</A>
+                <A href=Route::Blog.href(&base) attr:class="btn btn-secondary">"View All Posts"</A>
             </div>
         </section>
     }
 }
 
-
-
 #[component]
 pub fn BlogLoader() -> impl IntoView {
     let params = use_params_map();
-
-    let blog_data = move || {
-        let id = params.get().get("id").unwrap_or_default();
-        get_blog_by_id(&id)
-    };
+    let query = use_query_map();
+
+    let blog_data = Resource::new(
+        move || {
+            (
+                params.get().get("id").unwrap_or_default(),
+                query.get().get("preview"),
+            )
+        },
+        |(id, preview)| fetch_blog(id, preview),
+    );
 
     let base = use_context::<BasePath>().expect("BasePath context not found");
 
     view! {
-        {move || match blog_data() {
-            Some(data) => view! { <BlogDetail blog=data/> }.into_any(),
-            None => view! {
-                <div>
-                    <Navbar/>
-                    <div class="container" style="padding-top: 100px; margin-bottom: 30px; text-align: center;">
-                        <h1>"Project Not Found"</h1>
-                        <p>"The project you are looking for does not exist."</p>
-                        <A href=base.path("/") attr:style="margin-top: 20px" attr:class="btn btn-primary">"Return Home"</A>
-                    </div>
-                    <Footer/>
-                </div>
-            }.into_any(),
-        }}
+        <Suspense fallback=|| view! { <DetailSkeleton/> }>
+            {move || {
+                let base = base.clone();
+                Suspend::new(async move {
+                    match blog_data.await {
+                        Ok(Some(data)) => view! { <BlogDetail blog=data/> }.into_any(),
+                        Err(_) => view! {
+                            <div>
+                                <Navbar/>
+                                <div class="container" style="padding-top: 100px; margin-bottom: 30px;">
+                                    <LoadError what="this blog post" retry=move || blog_data.refetch()/>
+                                </div>
+                                <Footer/>
+                            </div>
+                        }.into_any(),
+                        Ok(None) => view! {
+                            <div>
+                                <Navbar/>
+                                <div class="container" style="padding-top: 100px; margin-bottom: 30px; text-align: center;">
+                                    <h1>"Blog Post Not Found"</h1>
+                                    <p>"The blog post you are looking for does not exist."</p>
+                                    <A href=Route::Home.href(&base) attr:style="margin-top: 20px" attr:class="btn btn-primary">"Return Home"</A>
+                                </div>
+                                <Footer/>
+                            </div>
+                        }.into_any(),
+                    }
+                })
+            }}
+        </Suspense>
     }
 }
 
 #[component]
-fn BlogDetail(blog: Blog) -> impl IntoView {
+pub fn BlogDetail(blog: Blog) -> impl IntoView {
     let base = use_context::<BasePath>().expect("BasePath context not found");
     view! {
         <div>
             <Navbar/>
             <section class="project-detail">
                 <div class="container">
-                    <A href=base.path("/#projects") attr:class="back-link">"← Back to Portfolio"</A>
+                    <A href=Route::Section(Section::Blogs).href(&base) attr:class="back-link">"← Back to Portfolio"</A>
 
-                    <div class="project-header">
+                    <StatusNotice status=blog.status/>
+                    <div class="project-header" style=transition_style(&Route::BlogPost(blog.id.clone()))>
                         <span class="tag">{blog.tag}</span>
                         <h1>{blog.title}</h1>
                         <p class="project-subtitle">{blog.subtitle}</p>
@@ -602,19 +1285,26 @@ fn AboutPage() -> impl IntoView {
 
 #[component]
 fn Photos() -> impl IntoView {
-    let photos = get_photos();
+    let photos = Resource::new(|| (), |_| fetch_photos());
     let base = use_context::<BasePath>().expect("BasePath context not found");
 
     view! {
         <section class="photos container" id="photos">
             <h2 class="section-title">"Photos"</h2>
             <div class="photos-grid">
-                {photos.into_iter().take(3).map(|photo| {
-                    view! { <PhotoCard photo=photo/> }
-                }).collect::<Vec<_>>()}
+                <Suspense fallback=|| view! { <PhotoSkeletons count=3/> }>
+                    {move || Suspend::new(async move {
+                        match photos.await {
+                            Ok(list) => list.into_iter().take(3).map(|photo| {
+                                view! { <PhotoCard photo=photo/> }
+                            }).collect_view().into_any(),
+                            Err(_) => view! { <LoadError what="photos" retry=move || photos.refetch()/> }.into_any(),
+                        }
+                    })}
+                </Suspense>
             </div>
             <div style="text-align: center; margin-top: 3rem;">
-                <A href=base.path("/photos") attr:class="btn btn-secondary">"View All Photos"</A>
+                <A href=Route::Photos.href(&base) attr:class="btn btn-secondary">"View All Photos"</A>
             </div>
         </section>
     }
@@ -630,7 +1320,7 @@ fn NotFoundPage() -> impl IntoView {
                 <div class="error-code">"404"</div>
                 <h1>"Oops! Page Not Found"</h1>
                 <p>"The page you are looking for might have been moved, deleted, or never existed in the first place."</p>
-                <A href=base.path("/") attr:class="btn btn-primary">"Return Home"</A>
+                <A href=Route::Home.href(&base) attr:class="btn btn-primary">"Return Home"</A>
             </main>
             <Footer/>
         </div>
@@ -676,23 +1366,27 @@ fn Footer() -> impl IntoView {
     }
 }
 
-#[component]
-pub fn Slideshow(images: Vec<String>) -> impl IntoView {
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+pub fn Slideshow(images: Vec<Photo>) -> impl IntoView {
     if images.is_empty() {
-        return view! {}.into_any();
+        return ().into_any();
     }
 
     let len = images.len();
     let (index, set_index) = signal(0);
     let prev = move |_| set_index.update(|i| *i = if *i == 0 { len - 1 } else { *i - 1 });
     let next = move |_| set_index.update(|i| *i = (*i + 1) % len);
-    let src = move || images.get(index.get()).cloned().unwrap_or_default();
+    let images = StoredValue::new(images);
+    let current =
+        move || images.with_value(|images| images.get(index.get()).cloned().unwrap_or_default());
 
     view! {
         <div class="slideshow">
             <button class="slide-btn slide-btn.prev" on:click=prev>"‹"</button>
-            <img class="project-image" src=src />
+            <img class="project-image" src=move || current().url alt=move || current().alt_text() />
             <button class="slide-btn slide-btn.next" on:click=next>"›"</button>
         </div>
-    }.into_any()
-}
\ No newline at end of file
+    }
+    .into_any()
+}
@@ -0,0 +1,323 @@
+//! Admin authentication.
+//!
+//! A single administrator signs in with the username and argon2 password hash from
+//! [`crate::config::AuthConfig`]. A successful login opens a server-side session that is
+//! referenced by an HTTP-only cookie and expires after the configured TTL. Every mutating
+//! admin server function must also present the session's CSRF token, see
+//! [`ssr::require_admin`].
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The signed-in administrator, as seen by the client.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AdminSession {
+    pub username: String,
+    /// Must accompany every mutating admin server function call.
+    pub csrf_token: String,
+    /// Unix timestamp in seconds after which the session is rejected.
+    pub expires_at: u64,
+}
+
+#[server]
+pub async fn current_admin() -> Result<Option<AdminSession>, ServerFnError> {
+    Ok(ssr::current_session().await?.map(|active| active.session))
+}
+
+#[server]
+pub async fn login(username: String, password: String) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::content::AuditAction;
+
+    require_same_origin().await?;
+    let auth = expect_context::<AuthState>();
+    if !auth.is_configured() {
+        return Err(ServerFnError::new(
+            "Admin login is not configured on this server.",
+        ));
+    }
+
+    let content = expect_context::<crate::content::Content>();
+    let Some(token) = auth.login(&username, &password) else {
+        // The username is whatever was typed, so keep what goes in the log short.
+        let attempted: String = username.chars().take(64).collect();
+        content.audit(&attempted, AuditAction::SignInFailed);
+        return Err(ServerFnError::new("Invalid username or password."));
+    };
+    content.audit(&username, AuditAction::SignedIn);
+    auth.set_session_cookie(&token);
+    leptos_axum::redirect(&crate::routing::Route::Admin.path());
+    Ok(())
+}
+
+#[server]
+pub async fn logout(csrf_token: String) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::content::AuditAction;
+
+    require_same_origin().await?;
+    let auth = expect_context::<AuthState>();
+    if let Some(active) = current_session().await? {
+        if !constant_time_eq(&active.session.csrf_token, &csrf_token) {
+            return Err(ServerFnError::new("Invalid CSRF token."));
+        }
+        auth.logout(&active.token);
+        expect_context::<crate::content::Content>()
+            .audit(&active.session.username, AuditAction::SignedOut);
+    }
+    auth.clear_session_cookie();
+    leptos_axum::redirect(&crate::routing::Route::AdminLogin.path());
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use std::collections::HashMap;
+    use std::sync::{Arc, RwLock};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+    use argon2::Argon2;
+    use axum::http::{header, HeaderMap, HeaderValue};
+    use leptos::prelude::*;
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    use super::AdminSession;
+    use crate::config::AuthConfig;
+
+    pub const SESSION_COOKIE: &str = "portfolio_session";
+
+    #[derive(Clone, Debug)]
+    struct Session {
+        username: String,
+        csrf_token: String,
+        expires_at: SystemTime,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum SameSite {
+        Strict,
+        Lax,
+    }
+
+    /// A session that belongs to the current request, together with its cookie token.
+    #[derive(Clone, Debug)]
+    pub struct ActiveSession {
+        pub token: String,
+        pub session: AdminSession,
+    }
+
+    /// Shared authentication state, provided to server functions through context.
+    #[derive(Clone)]
+    pub struct AuthState {
+        config: Arc<AuthConfig>,
+        sessions: Arc<RwLock<HashMap<String, Session>>>,
+    }
+
+    impl AuthState {
+        pub fn new(config: AuthConfig) -> Self {
+            Self {
+                config: Arc::new(config),
+                sessions: Arc::default(),
+            }
+        }
+
+        pub fn is_configured(&self) -> bool {
+            self.config.credentials.is_some()
+        }
+
+        /// Checks the credentials and opens a new session, returning its token.
+        pub fn login(&self, username: &str, password: &str) -> Option<String> {
+            let credentials = self.config.credentials.as_ref()?;
+            let hash = match PasswordHash::new(&credentials.password_hash) {
+                Ok(hash) => hash,
+                Err(err) => {
+                    leptos::logging::error!("invalid PORTFOLIO_ADMIN_PASSWORD_HASH: {err}");
+                    return None;
+                }
+            };
+
+            // Always run the (slow) password check so a wrong username takes as long as
+            // a wrong password.
+            let password_ok = Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok();
+            if !(password_ok && constant_time_eq(username, &credentials.username)) {
+                return None;
+            }
+
+            let token = random_token();
+            let session = Session {
+                username: credentials.username.clone(),
+                csrf_token: random_token(),
+                expires_at: SystemTime::now() + self.config.session_ttl,
+            };
+            let mut sessions = self.sessions.write().unwrap();
+            sessions.retain(|_, s| s.expires_at > SystemTime::now());
+            sessions.insert(token.clone(), session);
+            Some(token)
+        }
+
+        /// Looks up a live session by cookie token, dropping it if it has expired.
+        pub fn session(&self, token: &str) -> Option<AdminSession> {
+            let mut sessions = self.sessions.write().unwrap();
+            let session = sessions.get(token)?;
+            if session.expires_at <= SystemTime::now() {
+                sessions.remove(token);
+                return None;
+            }
+            Some(AdminSession {
+                username: session.username.clone(),
+                csrf_token: session.csrf_token.clone(),
+                expires_at: unix_seconds(session.expires_at),
+            })
+        }
+
+        pub fn logout(&self, token: &str) {
+            self.sessions.write().unwrap().remove(token);
+        }
+
+        pub fn set_session_cookie(&self, token: &str) {
+            self.set_cookie(
+                SESSION_COOKIE,
+                token,
+                self.config.session_ttl,
+                SameSite::Strict,
+            );
+        }
+
+        pub fn clear_session_cookie(&self) {
+            self.set_cookie(SESSION_COOKIE, "", Duration::ZERO, SameSite::Strict);
+        }
+
+        /// Adds an HTTP-only cookie to the response, marked `Secure` unless disabled in
+        /// the configuration.
+        pub fn set_cookie(&self, name: &str, value: &str, max_age: Duration, same_site: SameSite) {
+            let secure = if self.config.secure_cookies {
+                "; Secure"
+            } else {
+                ""
+            };
+            let same_site = match same_site {
+                SameSite::Strict => "Strict",
+                SameSite::Lax => "Lax",
+            };
+            let cookie = format!(
+                "{name}={value}; Path=/; Max-Age={}; HttpOnly; SameSite={same_site}{secure}",
+                max_age.as_secs()
+            );
+            if let (Some(response), Ok(value)) = (
+                use_context::<leptos_axum::ResponseOptions>(),
+                HeaderValue::from_str(&cookie),
+            ) {
+                response.append_header(header::SET_COOKIE, value);
+            }
+        }
+    }
+
+    /// Reads a cookie sent with the current request.
+    pub async fn request_cookie(name: &str) -> Result<Option<String>, ServerFnError> {
+        let headers: HeaderMap = leptos_axum::extract().await?;
+        Ok(cookie(&headers, name))
+    }
+
+    /// Resolves the session cookie sent with the current request, if it is still valid.
+    pub async fn current_session() -> Result<Option<ActiveSession>, ServerFnError> {
+        let auth = expect_context::<AuthState>();
+        Ok(request_cookie(SESSION_COOKIE).await?.and_then(|token| {
+            auth.session(&token)
+                .map(|session| ActiveSession { token, session })
+        }))
+    }
+
+    /// Guards a read-only admin server function: the request must carry a live session
+    /// cookie. Reads have no side effects, so no CSRF token is needed.
+    pub async fn require_session() -> Result<AdminSession, ServerFnError> {
+        current_session()
+            .await?
+            .map(|active| active.session)
+            .ok_or_else(|| ServerFnError::new("Not signed in."))
+    }
+
+    /// Guards a mutating admin server function: the request must be same-origin, carry a
+    /// live session cookie and present that session's CSRF token.
+    pub async fn require_admin(csrf_token: &str) -> Result<AdminSession, ServerFnError> {
+        require_same_origin().await?;
+        let active = current_session()
+            .await?
+            .ok_or_else(|| ServerFnError::new("Not signed in."))?;
+        if !constant_time_eq(&active.session.csrf_token, csrf_token) {
+            return Err(ServerFnError::new("Invalid CSRF token."));
+        }
+        Ok(active.session)
+    }
+
+    /// Rejects requests that a browser reports as coming from another site.
+    pub async fn require_same_origin() -> Result<(), ServerFnError> {
+        let headers: HeaderMap = leptos_axum::extract().await?;
+        let header_str = |name| {
+            headers
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+        };
+
+        if header_str(header::HeaderName::from_static("sec-fetch-site")) == Some("cross-site") {
+            return Err(ServerFnError::new("Cross-site request rejected."));
+        }
+        if let (Some(origin), Some(host)) = (header_str(header::ORIGIN), header_str(header::HOST)) {
+            let origin_host = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+            if origin_host != host {
+                return Err(ServerFnError::new("Cross-origin request rejected."));
+            }
+        }
+        Ok(())
+    }
+
+    /// Hashes a password into the PHC string expected in `PORTFOLIO_ADMIN_PASSWORD_HASH`.
+    pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+        let salt = SaltString::generate(&mut OsRng);
+        Ok(Argon2::default()
+            .hash_password(password.as_bytes(), &salt)?
+            .to_string())
+    }
+
+    /// Checks a password against a PHC string made by [`hash_password`].
+    pub fn verify_password(password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    }
+
+    pub fn constant_time_eq(a: &str, b: &str) -> bool {
+        a.len() == b.len()
+            && a.bytes()
+                .zip(b.bytes())
+                .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+                == 0
+    }
+
+    fn cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn random_token() -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn unix_seconds(time: SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+    }
+}
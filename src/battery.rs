@@ -0,0 +1,283 @@
+//! Battery pack sizing for the spot welder project's calculator.
+//!
+//! A pack is `series` groups of `parallel` cells: the groups add up the voltage, the
+//! cells within a group the capacity. Every cell terminal is spot welded to nickel strip,
+//! which is where the weld count and most of the weight beyond the cells come from.
+
+use std::fmt;
+
+/// Nickel strip as usually welded to cylindrical cells: 8 mm by 0.15 mm.
+pub const STRIP_WIDTH_MM: f64 = 8.0;
+pub const STRIP_THICKNESS_MM: f64 = 0.15;
+const NICKEL_G_PER_MM3: f64 = 0.0089;
+/// Weight of the BMS, wiring, insulation and wrap, as a share of the cells and strip.
+pub const OVERHEAD: f64 = 0.10;
+/// Largest layout the calculator accepts.
+pub const MAX_CELLS: usize = 2000;
+/// Most spot welds per cell terminal the calculator accepts.
+pub const MAX_WELDS_PER_TERMINAL: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Chemistry {
+    /// NMC or NCA lithium-ion.
+    LiIon,
+    LiFePo4,
+}
+
+impl Chemistry {
+    pub fn label(self) -> &'static str {
+        match self {
+            Chemistry::LiIon => "Li-ion (NMC)",
+            Chemistry::LiFePo4 => "LiFePO4",
+        }
+    }
+
+    pub fn nominal_voltage(self) -> f64 {
+        match self {
+            Chemistry::LiIon => 3.6,
+            Chemistry::LiFePo4 => 3.2,
+        }
+    }
+
+    /// Fully charged.
+    pub fn full_voltage(self) -> f64 {
+        match self {
+            Chemistry::LiIon => 4.2,
+            Chemistry::LiFePo4 => 3.65,
+        }
+    }
+}
+
+/// A common cell, to start from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellPreset {
+    pub name: &'static str,
+    pub chemistry: Chemistry,
+    pub capacity_mah: f64,
+    pub weight_g: f64,
+    pub diameter_mm: f64,
+}
+
+impl CellPreset {
+    pub fn cell(&self) -> Cell {
+        Cell {
+            chemistry: self.chemistry,
+            capacity_mah: self.capacity_mah,
+            voltage: self.chemistry.nominal_voltage(),
+            weight_g: self.weight_g,
+            diameter_mm: self.diameter_mm,
+        }
+    }
+}
+
+pub const PRESETS: [CellPreset; 4] = [
+    CellPreset {
+        name: "18650",
+        chemistry: Chemistry::LiIon,
+        capacity_mah: 3000.0,
+        weight_g: 47.0,
+        diameter_mm: 18.0,
+    },
+    CellPreset {
+        name: "21700",
+        chemistry: Chemistry::LiIon,
+        capacity_mah: 5000.0,
+        weight_g: 70.0,
+        diameter_mm: 21.0,
+    },
+    CellPreset {
+        name: "26650",
+        chemistry: Chemistry::LiFePo4,
+        capacity_mah: 3300.0,
+        weight_g: 86.0,
+        diameter_mm: 26.0,
+    },
+    CellPreset {
+        name: "32700",
+        chemistry: Chemistry::LiFePo4,
+        capacity_mah: 6000.0,
+        weight_g: 145.0,
+        diameter_mm: 32.0,
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub chemistry: Chemistry,
+    pub capacity_mah: f64,
+    /// Nominal.
+    pub voltage: f64,
+    pub weight_g: f64,
+    pub diameter_mm: f64,
+}
+
+/// What the pack has to deliver, at least.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Target {
+    pub voltage: f64,
+    pub capacity_ah: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackDesign {
+    pub series: usize,
+    pub parallel: usize,
+    pub nominal_voltage: f64,
+    pub full_voltage: f64,
+    pub capacity_ah: f64,
+    pub energy_wh: f64,
+    pub welds: usize,
+    pub strip_length_mm: f64,
+    pub cells_weight_g: f64,
+    pub nickel_weight_g: f64,
+    /// Cells, strip and [`OVERHEAD`].
+    pub total_weight_g: f64,
+}
+
+impl PackDesign {
+    pub fn cells(&self) -> usize {
+        self.series * self.parallel
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PackError {
+    /// A value that has to be above zero, by name.
+    NotPositive(&'static str),
+    TooManyCells(usize),
+    TooManyWelds(usize),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::NotPositive(what) => write!(f, "the {what} must be above zero"),
+            PackError::TooManyCells(cells) => write!(
+                f,
+                "that takes {cells} cells; the calculator stops at {MAX_CELLS}"
+            ),
+            PackError::TooManyWelds(welds) => write!(
+                f,
+                "{welds} welds per terminal is too many; the calculator stops at {MAX_WELDS_PER_TERMINAL}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// The smallest series/parallel layout of `cell` that reaches `target`, with each cell
+/// terminal spot welded `welds_per_terminal` times.
+pub fn design_pack(
+    cell: &Cell,
+    target: &Target,
+    welds_per_terminal: usize,
+) -> Result<PackDesign, PackError> {
+    for (value, what) in [
+        (cell.capacity_mah, "cell capacity"),
+        (cell.voltage, "cell voltage"),
+        (cell.weight_g, "cell weight"),
+        (cell.diameter_mm, "cell diameter"),
+        (target.voltage, "pack voltage"),
+        (target.capacity_ah, "pack capacity"),
+    ] {
+        if !(value > 0.0 && value.is_finite()) {
+            return Err(PackError::NotPositive(what));
+        }
+    }
+    if welds_per_terminal == 0 {
+        return Err(PackError::NotPositive("number of welds per terminal"));
+    }
+    if welds_per_terminal > MAX_WELDS_PER_TERMINAL {
+        return Err(PackError::TooManyWelds(welds_per_terminal));
+    }
+
+    let cell_ah = cell.capacity_mah / 1000.0;
+    // Rounded first, so 36 V from 3.6 V cells is 10 groups rather than 11.
+    let series = round_up(target.voltage / cell.voltage);
+    let parallel = round_up(target.capacity_ah / cell_ah);
+    let cells = series.saturating_mul(parallel);
+    if cells > MAX_CELLS {
+        return Err(PackError::TooManyCells(cells));
+    }
+
+    let nominal_voltage = series as f64 * cell.voltage;
+    let full_voltage = series as f64 * cell.voltage * cell.chemistry.full_voltage()
+        / cell.chemistry.nominal_voltage();
+    let capacity_ah = parallel as f64 * cell_ah;
+    // Strip runs across every cell on both ends.
+    let strip_length_mm = 2.0 * cells as f64 * cell.diameter_mm;
+    let nickel_weight_g = strip_length_mm * STRIP_WIDTH_MM * STRIP_THICKNESS_MM * NICKEL_G_PER_MM3;
+    let cells_weight_g = cells as f64 * cell.weight_g;
+    Ok(PackDesign {
+        series,
+        parallel,
+        nominal_voltage,
+        full_voltage,
+        capacity_ah,
+        energy_wh: nominal_voltage * capacity_ah,
+        welds: cells * 2 * welds_per_terminal,
+        strip_length_mm,
+        cells_weight_g,
+        nickel_weight_g,
+        total_weight_g: (cells_weight_g + nickel_weight_g) * (1.0 + OVERHEAD),
+    })
+}
+
+fn round_up(ratio: f64) -> usize {
+    let rounded = (ratio * 1e6).round() / 1e6;
+    (rounded.ceil() as usize).max(1)
+}
+
+/// Which way up a group's cells stand, seen from the top of the pack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    Positive,
+    Negative,
+}
+
+/// One piece of nickel strip on the top face, over the groups `first..=last`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Strip {
+    pub first: usize,
+    pub last: usize,
+    /// Strips at the ends of the pack carry its main terminals.
+    pub terminal: Option<Polarity>,
+}
+
+/// The top face of a pack: group polarities alternate so that each strip joins the
+/// positive ends of one group to the negative ends of the next. The bottom face has the
+/// other pairs, and the main positive terminal there when the group count is odd.
+pub fn top_face(series: usize) -> (Vec<Polarity>, Vec<Strip>) {
+    let polarities = (0..series)
+        .map(|group| {
+            if group % 2 == 0 {
+                Polarity::Negative
+            } else {
+                Polarity::Positive
+            }
+        })
+        .collect();
+    let mut strips = vec![Strip {
+        first: 0,
+        last: 0,
+        terminal: Some(Polarity::Negative),
+    }];
+    let mut group = 1;
+    while group < series {
+        strips.push(match group + 1 < series {
+            true => Strip {
+                first: group,
+                last: group + 1,
+                terminal: None,
+            },
+            false => Strip {
+                first: group,
+                last: group,
+                terminal: Some(Polarity::Positive),
+            },
+        });
+        group += 2;
+    }
+    (polarities, strips)
+}
@@ -0,0 +1,92 @@
+//! Checks every external link in the built-in content.
+//!
+//! ```text
+//! check-links [--offline] [--cache <file>] [--concurrency <n>] [--timeout <seconds>] [--max-age <hours>]
+//! ```
+//!
+//! Results are cached in `link-cache.json` by default; see [`portfolio::links`] for when
+//! the cache is used. Prints a report and fails if any link is broken, failed or could
+//! not be checked.
+
+use std::error::Error;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+
+use portfolio::content::{ContentSource, StaticContent};
+use portfolio::links::{check_links, collect_links, CheckOptions, LinkCache};
+
+const USAGE: &str = "usage: check-links [--offline] [--cache <file>] [--concurrency <n>] [--timeout <seconds>] [--max-age <hours>]";
+const DEFAULT_CACHE: &str = "link-cache.json";
+
+struct Args {
+    cache: String,
+    options: CheckOptions,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        cache: DEFAULT_CACHE.to_string(),
+        options: CheckOptions::default(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--offline" => parsed.options.offline = true,
+            "--cache" => parsed.cache = value()?,
+            "--concurrency" => parsed.options.concurrency = number(&arg, &value()?)?,
+            "--timeout" => parsed.options.timeout = Duration::from_secs(number(&arg, &value()?)?),
+            "--max-age" => {
+                parsed.options.max_age = chrono::Duration::try_hours(number(&arg, &value()?)?)
+                    .ok_or_else(|| format!("{arg} is out of range"))?
+            }
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn number<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} expects a number, got `{value}`"))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("check-links: {err}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("check-links: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns whether every link is fine.
+fn run(args: Args) -> Result<bool, Box<dyn Error>> {
+    let source = StaticContent;
+    let links = collect_links(&source.projects()?, &source.blogs()?);
+    let mut cache = LinkCache::load(&args.cache)?;
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    let report = runtime.block_on(check_links(links, &mut cache, &args.options))?;
+    if !args.options.offline {
+        cache.save()?;
+    }
+
+    println!("{report}");
+    let all_fine = report.problems().next().is_none();
+    Ok(all_fine)
+}
@@ -0,0 +1,182 @@
+//! Content tooling for the portfolio.
+//!
+//! ```text
+//! portfolio export <dir>                        Write projects.json, blogs.json and photos.json into <dir>
+//! portfolio lint [<public dir>] [<media dir>]   Check the built-in content (defaults: public, media)
+//! portfolio mirror <site dir> [--store <dir>] [--accept-changes] [--offline]
+//!                                               Mirror poster PDFs and papers into an exported site
+//! ```
+//!
+//! The CSR build runs `export` as a Trunk post-build hook so the static site fetches its
+//! content at runtime instead of compiling it in. Only content that is public at export
+//! time is written: drafts and anything scheduled for later stay out of the public files,
+//! and private projects are written as teasers only.
+//!
+//! `lint` checks every project, blog post and photo, drafts included: ids must be unique
+//! and usable in URLs, images must exist and have a caption and alt text, and links must
+//! be well-formed URLs or files the site serves. It prints every problem and fails if
+//! there are any.
+//!
+//! `mirror` (built with the `mirror` feature) runs after `export`: it downloads the
+//! external posters and papers of `<site dir>/content/projects.json` into the store
+//! (default `mirror/`, committed with the site), copies them into `<site dir>/mirror/` and
+//! rewrites the links to them. Upstream files that changed since they were stored are
+//! reported and left alone unless `--accept-changes` is given, and a stored file that no
+//! longer matches its recorded checksum fails the run. The Trunk hook runs it `--offline`;
+//! the deploy workflow checks upstream.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::Utc;
+use portfolio::content::{
+    lint_content, Assets, ContentSource, StaticContent, BLOGS_FILE, MEDIA_URL_DIR, PHOTOS_FILE,
+    PROJECTS_FILE,
+};
+use serde::Serialize;
+
+const USAGE: &str = "usage: portfolio export <dir>
+       portfolio lint [<public dir>] [<media dir>]
+       portfolio mirror <site dir> [--store <dir>] [--accept-changes] [--offline]";
+const PUBLIC_DIR: &str = "public";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["export", dir] => export(&StaticContent, Path::new(dir)),
+        ["lint", dirs @ ..] if dirs.len() <= 2 => {
+            let assets = Assets {
+                public_dir: PathBuf::from(dirs.first().copied().unwrap_or(PUBLIC_DIR)),
+                media_dir: PathBuf::from(dirs.get(1).copied().unwrap_or(MEDIA_URL_DIR)),
+            };
+            lint(&StaticContent, &assets)
+        }
+        #[cfg(feature = "mirror")]
+        ["mirror", site, flags @ ..] => match mirror::parse_flags(flags) {
+            Some((store, options)) => mirror::run(Path::new(site), Path::new(store), &options),
+            None => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("portfolio: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn export(source: &dyn ContentSource, dir: &Path) -> Result<(), Box<dyn Error>> {
+    let now = Utc::now();
+    let projects: Vec<_> = source
+        .projects()?
+        .into_iter()
+        .filter(|p| p.status.is_public(now))
+        .map(|p| {
+            if p.is_private() {
+                p.teaser()
+            } else {
+                p.without_secrets()
+            }
+        })
+        .collect();
+    let mut blogs = source.blogs()?;
+    blogs.retain(|b| b.status.is_public(now));
+
+    fs::create_dir_all(dir)?;
+    write_json(&dir.join(PROJECTS_FILE), &projects)?;
+    write_json(&dir.join(BLOGS_FILE), &blogs)?;
+    write_json(&dir.join(PHOTOS_FILE), &source.photos()?)?;
+    println!("exported content to {}", dir.display());
+    Ok(())
+}
+
+fn lint(source: &dyn ContentSource, assets: &Assets) -> Result<(), Box<dyn Error>> {
+    let problems = lint_content(
+        &source.projects()?,
+        &source.blogs()?,
+        &source.photos()?,
+        assets,
+    );
+    for problem in &problems {
+        println!("{problem}");
+    }
+    match problems.len() {
+        0 => {
+            println!("no problems found");
+            Ok(())
+        }
+        count => Err(format!("found {count} problem(s)").into()),
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), Box<dyn Error>> {
+    fs::write(path, serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
+
+#[cfg(feature = "mirror")]
+mod mirror {
+    use std::error::Error;
+    use std::fs;
+    use std::path::Path;
+
+    use portfolio::content::{CONTENT_URL_DIR, PROJECTS_FILE};
+    use portfolio::data::Project;
+    use portfolio::mirror::{publish, update_store, Manifest, MirrorOptions};
+
+    const DEFAULT_STORE: &str = "mirror";
+
+    pub fn parse_flags<'a>(flags: &[&'a str]) -> Option<(&'a str, MirrorOptions)> {
+        let mut store = DEFAULT_STORE;
+        let mut options = MirrorOptions::default();
+        let mut flags = flags.iter();
+        while let Some(flag) = flags.next() {
+            match *flag {
+                "--store" => store = flags.next()?,
+                "--accept-changes" => options.accept_changes = true,
+                "--offline" => options.offline = true,
+                _ => return None,
+            }
+        }
+        Some((store, options))
+    }
+
+    pub fn run(site: &Path, store: &Path, options: &MirrorOptions) -> Result<(), Box<dyn Error>> {
+        let projects_file = site.join(CONTENT_URL_DIR).join(PROJECTS_FILE);
+        let mut projects: Vec<Project> = serde_json::from_slice(&fs::read(&projects_file)?)?;
+        let mut manifest = Manifest::load(store)?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let report = runtime.block_on(update_store(&projects, &mut manifest, options))?;
+        manifest.save()?;
+        publish(&mut projects, &manifest, site)?;
+        super::write_json(&projects_file, &projects)?;
+
+        println!("{report}");
+        // Surface flagged files as annotations on the workflow run.
+        if std::env::var_os("GITHUB_ACTIONS").is_some() {
+            for result in report.flagged() {
+                println!("::warning title=Mirror::{}: {}", result.url, result.outcome);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+//! Renders the content blocks of a project's case study.
+//!
+//! The `body` of rich text and callout blocks uses a small markup:
+//!
+//! - a blank line starts a new paragraph
+//! - lines starting with `- ` form a bulleted list
+//! - `**bold**`, `*emphasis*`, `` `code` `` and `[text](url)` within a line
+//!
+//! The markup is turned into elements directly, never into HTML, so content cannot
+//! inject markup or scripts.
+
+use std::sync::Arc;
+
+use leptos::prelude::*;
+use url::Url;
+
+use crate::app::PhotoCard;
+use crate::chart::SvgChart;
+use crate::data::{Block, Text, Tone};
+
+/// The blocks in order, each as its own project section.
+#[component]
+pub fn Blocks(blocks: Arc<[Block]>) -> impl IntoView {
+    blocks.iter().cloned().map(block_view).collect_view()
+}
+
+fn block_view(block: Block) -> AnyView {
+    let heading = |heading: Option<Text>| heading.map(|heading| view! { <h2>{heading}</h2> });
+    match block {
+        Block::RichText { heading: title, body } => view! {
+            <div class="project-section block-text">
+                {heading(title)}
+                <RichText text=body/>
+            </div>
+        }
+        .into_any(),
+        Block::Image(photo) => view! {
+            <figure class="project-section block-image">
+                <img src=photo.url.clone() alt=photo.alt_text() loading="lazy"/>
+                {(!photo.caption.is_empty()).then(|| view! { <figcaption>{photo.caption.clone()}</figcaption> })}
+            </figure>
+        }
+        .into_any(),
+        Block::Gallery { heading: title, photos } => view! {
+            <div class="project-section block-gallery">
+                {heading(title)}
+                <div class="photos-grid">
+                    {photos.iter().cloned().map(|photo| view! { <PhotoCard photo/> }).collect_view()}
+                </div>
+            </div>
+        }
+        .into_any(),
+        Block::Code { language, caption, code } => view! {
+            <figure class="project-section block-code">
+                {language.map(|language| view! { <span class="block-code-language">{language}</span> })}
+                <pre><code>{code}</code></pre>
+                {caption.map(|caption| view! { <figcaption>{caption}</figcaption> })}
+            </figure>
+        }
+        .into_any(),
+        Block::Quote { text, attribution } => view! {
+            <figure class="project-section block-quote">
+                <blockquote>{text}</blockquote>
+                {attribution.map(|attribution| view! { <figcaption>"— "{attribution}</figcaption> })}
+            </figure>
+        }
+        .into_any(),
+        Block::Metrics { heading: title, items } => view! {
+            <div class="project-section block-metrics">
+                {heading(title)}
+                <div class="metrics-grid">
+                    {items.iter().cloned().map(|metric| view! {
+                        <div class="metric">
+                            <span class="metric-value">{metric.value}</span>
+                            <span class="metric-label">{metric.label}</span>
+                        </div>
+                    }).collect_view()}
+                </div>
+            </div>
+        }
+        .into_any(),
+        Block::Video { url, title } => {
+            let player = match embed_url(&url) {
+                Some(src) => view! {
+                    <div class="block-video-frame">
+                        <iframe
+                            src=src
+                            title=title.clone()
+                            allow="fullscreen; picture-in-picture"
+                            allowfullscreen=true
+                        ></iframe>
+                    </div>
+                }
+                .into_any(),
+                None => view! { <video src=url controls=true preload="metadata" title=title.clone()></video> }.into_any(),
+            };
+            view! {
+                <figure class="project-section block-video">
+                    {player}
+                    {(!title.is_empty()).then(|| view! { <figcaption>{title.clone()}</figcaption> })}
+                </figure>
+            }
+            .into_any()
+        }
+        Block::Callout { tone, title, body } => view! {
+            <aside class=format!("project-section block-callout callout-{}", tone_class(tone)) role="note">
+                {title.map(|title| view! { <strong class="callout-title">{title}</strong> })}
+                <RichText text=body/>
+            </aside>
+        }
+        .into_any(),
+        Block::Chart(chart) => view! {
+            <div class="project-section block-chart">
+                <SvgChart chart/>
+            </div>
+        }
+        .into_any(),
+    }
+}
+
+fn tone_class(tone: Tone) -> &'static str {
+    match tone {
+        Tone::Info => "info",
+        Tone::Success => "success",
+        Tone::Warning => "warning",
+    }
+}
+
+/// The player page for a YouTube or Vimeo video, or `None` for anything else.
+fn embed_url(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url
+        .host_str()?
+        .trim_start_matches("www.")
+        .trim_start_matches("m.");
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    let id = |id: &str| {
+        (!id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .then(|| id.to_string())
+    };
+    match host {
+        "youtube.com" => {
+            let video = match segments.next()? {
+                "watch" => url
+                    .query_pairs()
+                    .find(|(key, _)| key == "v")
+                    .map(|(_, value)| value.into_owned())?,
+                "embed" | "shorts" | "live" => segments.next()?.to_string(),
+                _ => return None,
+            };
+            id(&video).map(|video| format!("https://www.youtube-nocookie.com/embed/{video}"))
+        }
+        "youtu.be" => id(segments.next()?)
+            .map(|video| format!("https://www.youtube-nocookie.com/embed/{video}")),
+        "vimeo.com" => segments
+            .next()
+            .filter(|video| video.chars().all(|c| c.is_ascii_digit()))
+            .map(|video| format!("https://player.vimeo.com/video/{video}")),
+        _ => None,
+    }
+}
+
+/// Text in the markup described in the module docs.
+#[component]
+pub fn RichText(#[prop(into)] text: Text) -> impl IntoView {
+    paragraphs(&text)
+        .into_iter()
+        .map(|paragraph| match paragraph {
+            Paragraph::Text(text) => view! { <p>{inline_view(&text)}</p> }.into_any(),
+            Paragraph::List(items) => view! {
+                <ul>{items.into_iter().map(|item| view! { <li>{inline_view(item)}</li> }).collect_view()}</ul>
+            }
+            .into_any(),
+        })
+        .collect_view()
+}
+
+enum Paragraph<'a> {
+    /// The paragraph's lines, joined by spaces.
+    Text(String),
+    List(Vec<&'a str>),
+}
+
+fn paragraphs(text: &str) -> Vec<Paragraph<'_>> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<Paragraph> = None;
+    for line in text.lines().map(str::trim) {
+        match (line.strip_prefix("- "), &mut current) {
+            _ if line.is_empty() => paragraphs.extend(current.take()),
+            (Some(item), Some(Paragraph::List(items))) => items.push(item.trim()),
+            (Some(item), _) => {
+                paragraphs.extend(current.take());
+                current = Some(Paragraph::List(vec![item.trim()]));
+            }
+            (None, Some(Paragraph::Text(text))) => {
+                text.push(' ');
+                text.push_str(line);
+            }
+            (None, _) => {
+                paragraphs.extend(current.take());
+                current = Some(Paragraph::Text(line.to_string()));
+            }
+        }
+    }
+    paragraphs.extend(current);
+    paragraphs
+}
+
+enum Inline<'a> {
+    Text(&'a str),
+    Strong(&'a str),
+    Emphasis(&'a str),
+    Code(&'a str),
+    Link { text: &'a str, url: &'a str },
+}
+
+/// Splits a line into runs of plain and marked-up text. Markers without a closing
+/// counterpart are kept as plain text.
+fn inlines(text: &str) -> Vec<Inline<'_>> {
+    let mut inlines = Vec::new();
+    let (mut plain, mut at) = (0, 0);
+    while at < text.len() {
+        let rest = &text[at..];
+        let enclosed = |open: &str, close: &str| {
+            let inner = rest.strip_prefix(open)?;
+            let end = inner.find(close).filter(|&end| end > 0)?;
+            Some((&inner[..end], open.len() + end + close.len()))
+        };
+        let found = if rest.starts_with("**") {
+            enclosed("**", "**").map(|(inner, len)| (Inline::Strong(inner), len))
+        } else if rest.starts_with('*') {
+            enclosed("*", "*").map(|(inner, len)| (Inline::Emphasis(inner), len))
+        } else if rest.starts_with('`') {
+            enclosed("`", "`").map(|(inner, len)| (Inline::Code(inner), len))
+        } else if rest.starts_with('[') {
+            enclosed("[", "](").and_then(|(link_text, len)| {
+                let (url, url_len) = rest[len..]
+                    .find(')')
+                    .map(|end| (&rest[len..len + end], end + 1))?;
+                Some((
+                    Inline::Link {
+                        text: link_text,
+                        url,
+                    },
+                    len + url_len,
+                ))
+            })
+        } else {
+            None
+        };
+        match found {
+            Some((inline, len)) => {
+                if plain < at {
+                    inlines.push(Inline::Text(&text[plain..at]));
+                }
+                inlines.push(inline);
+                at += len;
+                plain = at;
+            }
+            None => at += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if plain < text.len() {
+        inlines.push(Inline::Text(&text[plain..]));
+    }
+    inlines
+}
+
+fn inline_view(text: &str) -> AnyView {
+    inlines(text)
+        .into_iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.to_string().into_any(),
+            Inline::Strong(text) => view! { <strong>{text.to_string()}</strong> }.into_any(),
+            Inline::Emphasis(text) => view! { <em>{text.to_string()}</em> }.into_any(),
+            Inline::Code(text) => view! { <code>{text.to_string()}</code> }.into_any(),
+            Inline::Link { text, url } if is_safe_link(url) => {
+                let external = url.starts_with("http://") || url.starts_with("https://");
+                view! {
+                    <a
+                        href=url.to_string()
+                        target=external.then_some("_blank")
+                        rel=external.then_some("noopener noreferrer")
+                    >
+                        {text.to_string()}
+                    </a>
+                }
+                .into_any()
+            }
+            Inline::Link { text, .. } => text.to_string().into_any(),
+        })
+        .collect_view()
+        .into_any()
+}
+
+/// Web, mail and relative links; anything with another scheme, such as `javascript:`,
+/// is shown as plain text.
+fn is_safe_link(url: &str) -> bool {
+    match url.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            matches!(
+                scheme.to_ascii_lowercase().as_str(),
+                "http" | "https" | "mailto"
+            )
+        }
+        _ => !url.is_empty(),
+    }
+}
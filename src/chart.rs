@@ -0,0 +1,467 @@
+//! SVG charts of the numeric results in project content.
+//!
+//! A chart renders the same on the server and in the browser. It carries a short
+//! description and a visually hidden table of its data for screen readers; once
+//! hydrated, hovering or focusing a bar or point shows its value in a tooltip.
+
+use leptos::prelude::*;
+
+use crate::data::{Chart, ChartKind, Series};
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 360.0;
+// Room around the plot for tick labels and axis titles.
+const LEFT: f64 = 64.0;
+const RIGHT: f64 = 16.0;
+const TOP: f64 = 16.0;
+const BOTTOM: f64 = 56.0;
+/// Series colours repeat after this many; see `.chart-series-*` in the stylesheet.
+const SERIES_COLORS: usize = 6;
+
+/// A bar chart of `values` by category, or a line or scatter chart.
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+pub fn SvgChart(chart: Chart) -> impl IntoView {
+    let plot = Plot::new(&chart);
+    let (active, set_active) = signal(None::<usize>);
+    let tips = StoredValue::new(plot.marks.iter().map(Mark::tip).collect::<Vec<_>>());
+
+    let grid = plot
+        .y_ticks
+        .into_iter()
+        .map(|(y, label)| {
+            view! {
+                <line class="chart-grid" x1=LEFT x2=WIDTH - RIGHT y1=y y2=y></line>
+                <text class="chart-tick chart-tick-y" x=LEFT - 8.0 y=y + 4.0>{label}</text>
+            }
+        })
+        .collect_view();
+    let x_ticks = plot
+        .x_ticks
+        .into_iter()
+        .map(|(x, label)| {
+            view! {
+                <text class="chart-tick chart-tick-x" x=x y=HEIGHT - BOTTOM + 20.0>{label}</text>
+            }
+        })
+        .collect_view();
+    let lines = plot.lines.into_iter().map(|(series, points)| view! {
+        <polyline class=format!("chart-line chart-series-{}", series % SERIES_COLORS) points=points></polyline>
+    }).collect_view();
+    let marks = plot
+        .marks
+        .into_iter()
+        .enumerate()
+        .map(|(index, mark)| {
+            let class = format!("chart-series-{}", mark.series % SERIES_COLORS);
+            let show = move || set_active.set(Some(index));
+            let hide = move || set_active.set(None);
+            if mark.width > 0.0 {
+                view! {
+                    <rect
+                        class=format!("chart-bar {class}")
+                        x=mark.x
+                        y=mark.y
+                        width=mark.width
+                        height=mark.height
+                        tabindex="0"
+                        aria-label=mark.label
+                        on:mouseenter=move |_| show()
+                        on:mouseleave=move |_| hide()
+                        on:focus=move |_| show()
+                        on:blur=move |_| hide()
+                    ></rect>
+                }
+                .into_any()
+            } else {
+                view! {
+                    <circle
+                        class=format!("chart-point {class}")
+                        cx=mark.x
+                        cy=mark.y
+                        r=5
+                        tabindex="0"
+                        aria-label=mark.label
+                        on:mouseenter=move |_| show()
+                        on:mouseleave=move |_| hide()
+                        on:focus=move |_| show()
+                        on:blur=move |_| hide()
+                    ></circle>
+                }
+                .into_any()
+            }
+        })
+        .collect_view();
+    let x_label = chart.x_label.clone().map(|label| view! {
+        <text class="chart-axis-label" x=LEFT + (WIDTH - LEFT - RIGHT) / 2.0 y=HEIGHT - 12.0>{label}</text>
+    });
+    let y_label = chart.y_label.clone().map(|label| view! {
+        <text class="chart-axis-label" transform="rotate(-90)" x=-(TOP + (HEIGHT - TOP - BOTTOM) / 2.0) y=16>{label}</text>
+    });
+    let tooltip = move || {
+        let (x, y, label) = tips.with_value(|tips| tips.get(active.get()?).cloned())?;
+        let style = format!(
+            "left: {:.2}%; top: {:.2}%",
+            x / WIDTH * 100.0,
+            y / HEIGHT * 100.0
+        );
+        Some(view! { <div class="chart-tooltip" style=style>{label}</div> })
+    };
+    let legend = (chart.series.len() > 1).then(|| view! {
+        <ul class="chart-legend">
+            {chart.series.iter().enumerate().map(|(index, series)| view! {
+                <li>
+                    <span class=format!("chart-swatch chart-series-{}", index % SERIES_COLORS)></span>
+                    {series.name.clone()}
+                </li>
+            }).collect_view()}
+        </ul>
+    });
+
+    view! {
+        <figure class="chart">
+            {(!chart.title.is_empty()).then(|| view! { <figcaption class="chart-title">{chart.title.clone()}</figcaption> })}
+            <div class="chart-plot">
+                <svg viewBox=format!("0 0 {WIDTH} {HEIGHT}") role="img" aria-label=describe(&chart)>
+                    {grid}
+                    <line class="chart-axis" x1=LEFT x2=WIDTH - RIGHT y1=HEIGHT - BOTTOM y2=HEIGHT - BOTTOM></line>
+                    {x_ticks}
+                    {x_label}
+                    {y_label}
+                    {lines}
+                    {marks}
+                </svg>
+                {tooltip}
+            </div>
+            {legend}
+            <DataTable chart/>
+        </figure>
+    }
+}
+
+/// The chart's numbers for screen readers.
+#[component]
+fn DataTable(chart: Chart) -> impl IntoView {
+    let unit = chart.unit.clone().unwrap_or_default();
+    let format = &move |value: f64| format!("{}{unit}", format_value(value));
+    let (head, rows) = if chart.is_categorical() {
+        let head = std::iter::once(chart.x_label.clone().unwrap_or_else(|| "Category".into()))
+            .chain(chart.series.iter().map(|series| series.name.clone()))
+            .map(|name| view! { <th scope="col">{name}</th> })
+            .collect_view();
+        let rows = chart
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| {
+                view! {
+                    <tr>
+                        <th scope="row">{category.clone()}</th>
+                        {chart.series.iter().map(|series| view! {
+                            <td>{series.values.get(index).map(|&value| format(value))}</td>
+                        }).collect_view()}
+                    </tr>
+                }
+            })
+            .collect_view();
+        (head.into_any(), rows.into_any())
+    } else {
+        let head = view! {
+            <th scope="col">"Series"</th>
+            <th scope="col">{chart.x_label.clone().unwrap_or_else(|| "x".into())}</th>
+            <th scope="col">{chart.y_label.clone().unwrap_or_else(|| "y".into())}</th>
+        };
+        let rows = chart
+            .series
+            .iter()
+            .flat_map(|series| {
+                series.points.iter().map(move |&[x, y]| {
+                    view! {
+                        <tr>
+                            <th scope="row">{series.name.clone()}</th>
+                            <td>{format_value(x)}</td>
+                            <td>{format(y)}</td>
+                        </tr>
+                    }
+                })
+            })
+            .collect_view();
+        (head.into_any(), rows.into_any())
+    };
+
+    view! {
+        <table class="visually-hidden">
+            <caption>{chart.title.clone()}</caption>
+            <thead><tr>{head}</tr></thead>
+            <tbody>{rows}</tbody>
+        </table>
+    }
+}
+
+/// What the chart shows, in one sentence.
+fn describe(chart: &Chart) -> String {
+    let kind = match chart.kind {
+        ChartKind::Bar => "Bar chart",
+        ChartKind::Line => "Line chart",
+        ChartKind::Scatter => "Scatter chart",
+    };
+    let names: Vec<&str> = chart
+        .series
+        .iter()
+        .map(|series| &*series.name)
+        .filter(|name| !name.is_empty())
+        .collect();
+    let mut text = if chart.title.is_empty() {
+        kind.to_string()
+    } else {
+        format!("{kind}: {}", chart.title)
+    };
+    if !names.is_empty() {
+        text.push_str(&format!(", comparing {}", names.join(", ")));
+    }
+    if chart.is_categorical() {
+        text.push_str(&format!(" across {} categories", chart.categories.len()));
+    }
+    text.push_str(". The data follows as a table.");
+    text
+}
+
+/// A bar or point, in SVG coordinates. Points have no width.
+struct Mark {
+    series: usize,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    label: String,
+}
+
+impl Mark {
+    /// Where its tooltip points to, and what it says.
+    fn tip(&self) -> (f64, f64, String) {
+        (self.x + self.width / 2.0, self.y, self.label.clone())
+    }
+}
+
+/// Everything drawn inside the SVG, laid out.
+struct Plot {
+    y_ticks: Vec<(f64, String)>,
+    x_ticks: Vec<(f64, String)>,
+    /// The `points` attribute of each line, by series.
+    lines: Vec<(usize, String)>,
+    marks: Vec<Mark>,
+}
+
+impl Plot {
+    fn new(chart: &Chart) -> Self {
+        let (left, right, top, bottom) = (LEFT, WIDTH - RIGHT, TOP, HEIGHT - BOTTOM);
+        let unit = chart.unit.as_deref().unwrap_or_default();
+        let label = |series: &Series, at: &str, value: f64| {
+            let value = format!("{}{unit}", format_value(value));
+            match series.name.is_empty() {
+                true => format!("{at}: {value}"),
+                false => format!("{}, {at}: {value}", series.name),
+            }
+        };
+
+        let categorical = chart.is_categorical();
+        let ys: Vec<f64> = match categorical {
+            true => chart
+                .series
+                .iter()
+                .flat_map(|series| series.values.iter().copied())
+                .collect(),
+            false => chart
+                .series
+                .iter()
+                .flat_map(|series| series.points.iter().map(|point| point[1]))
+                .collect(),
+        };
+        let (mut min, mut max) = bounds(ys);
+        if chart.kind == ChartKind::Bar {
+            (min, max) = (min.min(0.0), max.max(0.0));
+        }
+        let y = Axis::new(min, max, bottom, top);
+        let mut plot = Plot {
+            y_ticks: y
+                .ticks()
+                .into_iter()
+                .map(|tick| (y.at(tick), format_tick(tick, y.step)))
+                .collect(),
+            x_ticks: Vec::new(),
+            lines: Vec::new(),
+            marks: Vec::new(),
+        };
+
+        if categorical {
+            let band = (right - left) / chart.categories.len().max(1) as f64;
+            let bar = band * 0.8 / chart.series.len().max(1) as f64;
+            plot.x_ticks = chart
+                .categories
+                .iter()
+                .enumerate()
+                .map(|(i, category)| (left + (i as f64 + 0.5) * band, category.to_string()))
+                .collect();
+            for (s, series) in chart.series.iter().enumerate() {
+                let mut line = Vec::new();
+                for (i, (&value, category)) in series
+                    .values
+                    .iter()
+                    .zip(chart.categories.iter())
+                    .enumerate()
+                {
+                    let label = label(series, category, value);
+                    if chart.kind == ChartKind::Bar {
+                        let (high, low) = (y.at(value.max(0.0)), y.at(value.min(0.0)));
+                        plot.marks.push(Mark {
+                            series: s,
+                            x: left + (i as f64 + 0.1) * band + s as f64 * bar,
+                            y: high,
+                            width: bar,
+                            height: low - high,
+                            label,
+                        });
+                    } else {
+                        let (cx, cy) = (left + (i as f64 + 0.5) * band, y.at(value));
+                        line.push((cx, cy));
+                        plot.marks.push(Mark {
+                            series: s,
+                            x: cx,
+                            y: cy,
+                            width: 0.0,
+                            height: 0.0,
+                            label,
+                        });
+                    }
+                }
+                if line.len() > 1 {
+                    plot.lines.push((s, points_attribute(&line)));
+                }
+            }
+        } else {
+            let (min, max) = bounds(
+                chart
+                    .series
+                    .iter()
+                    .flat_map(|series| series.points.iter().map(|point| point[0]))
+                    .collect(),
+            );
+            let x = Axis::new(min, max, left, right);
+            plot.x_ticks = x
+                .ticks()
+                .into_iter()
+                .map(|tick| (x.at(tick), format_tick(tick, x.step)))
+                .collect();
+            for (s, series) in chart.series.iter().enumerate() {
+                let mut points = series.points.to_vec();
+                if chart.kind == ChartKind::Line {
+                    points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+                }
+                let mut line = Vec::new();
+                for [px, py] in points {
+                    let (cx, cy) = (x.at(px), y.at(py));
+                    line.push((cx, cy));
+                    let label = label(series, &format_value(px), py);
+                    plot.marks.push(Mark {
+                        series: s,
+                        x: cx,
+                        y: cy,
+                        width: 0.0,
+                        height: 0.0,
+                        label,
+                    });
+                }
+                if chart.kind == ChartKind::Line && line.len() > 1 {
+                    plot.lines.push((s, points_attribute(&line)));
+                }
+            }
+        }
+        plot
+    }
+}
+
+/// A linear mapping from data values onto one SVG axis, rounded out to whole ticks.
+struct Axis {
+    lo: f64,
+    hi: f64,
+    step: f64,
+    from: f64,
+    to: f64,
+}
+
+impl Axis {
+    /// Spans at least `min..=max`, drawn from the SVG coordinate `from` to `to`.
+    fn new(min: f64, max: f64, from: f64, to: f64) -> Self {
+        let (min, max) = if min < max {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        };
+        let step = nice_step((max - min) / 5.0);
+        Self {
+            lo: (min / step).floor() * step,
+            hi: (max / step).ceil() * step,
+            step,
+            from,
+            to,
+        }
+    }
+
+    fn at(&self, value: f64) -> f64 {
+        self.from + (value - self.lo) / (self.hi - self.lo) * (self.to - self.from)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        let count = ((self.hi - self.lo) / self.step).round() as usize;
+        (0..=count)
+            .map(|i| self.lo + i as f64 * self.step)
+            .collect()
+    }
+}
+
+/// The 1, 2 or 5 times a power of ten nearest above `raw`.
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let factor = match raw / magnitude {
+        n if n <= 1.0 => 1.0,
+        n if n <= 2.0 => 2.0,
+        n if n <= 5.0 => 5.0,
+        _ => 10.0,
+    };
+    factor * magnitude
+}
+
+fn bounds(values: Vec<f64>) -> (f64, f64) {
+    let finite = values.into_iter().filter(|value| value.is_finite());
+    finite
+        .fold(None, |bounds: Option<(f64, f64)>, value| match bounds {
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+            None => Some((value, value)),
+        })
+        .unwrap_or((0.0, 1.0))
+}
+
+fn points_attribute(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{x:.1},{y:.1}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A tick label with as many decimals as the step between ticks needs.
+fn format_tick(value: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    format!("{value:.decimals$}")
+}
+
+/// A value with up to three decimals, without trailing zeros.
+fn format_value(value: f64) -> String {
+    let text = format!("{value:.3}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,172 @@
+//! Server configuration read from the environment at startup.
+//!
+//! | Variable                        | Meaning                                                   |
+//! |---------------------------------|-----------------------------------------------------------|
+//! | `PORTFOLIO_ADMIN_USERNAME`      | Username accepted by the admin login                      |
+//! | `PORTFOLIO_ADMIN_PASSWORD_HASH` | Argon2 PHC string (`portfolio-bin hash-password`)         |
+//! | `PORTFOLIO_SESSION_TTL_MINUTES` | Lifetime of an admin session, defaults to 60              |
+//! | `PORTFOLIO_INSECURE_COOKIES`    | Set to `1` to drop the `Secure` cookie flag locally       |
+//! | `PORTFOLIO_CONTENT_BACKEND`     | `directory` (default), `sqlite` or `static`               |
+//! | `PORTFOLIO_CONTENT_DIR`         | Directory backend location, defaults to `content`         |
+//! | `PORTFOLIO_CONTENT_DB`          | SQLite database path, defaults to `content.db`            |
+//! | `PORTFOLIO_LINK_SECRET`         | Key for signing preview and invite links; random if unset |
+//! | `PORTFOLIO_PREVIEW_TTL_HOURS`   | Lifetime of a preview link, defaults to 72                |
+//! | `PORTFOLIO_INVITE_TTL_DAYS`     | Lifetime of an invite link or unlock, defaults to 30      |
+//! | `PORTFOLIO_MEDIA_DIR`           | Where uploaded media is stored, defaults to `media`       |
+//! | `PORTFOLIO_MAX_UPLOAD_MB`       | Largest file the media library accepts, defaults to 25    |
+
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_SESSION_TTL_MINUTES: u64 = 60;
+const DEFAULT_CONTENT_DIR: &str = "content";
+const DEFAULT_CONTENT_DB: &str = "content.db";
+const DEFAULT_PREVIEW_TTL_HOURS: u64 = 72;
+const DEFAULT_INVITE_TTL_DAYS: u64 = 30;
+const DEFAULT_MEDIA_DIR: &str = "media";
+const DEFAULT_MAX_UPLOAD_MB: u64 = 25;
+
+#[derive(Clone, Debug)]
+pub struct SiteConfig {
+    pub auth: AuthConfig,
+    pub content: ContentBackend,
+    pub links: LinkConfig,
+    pub media: MediaConfig,
+}
+
+/// Where the server reads and stores content.
+#[derive(Clone, Debug)]
+pub enum ContentBackend {
+    /// The built-in content only; the admin editor cannot save.
+    Static,
+    /// JSON files in a directory.
+    Directory(PathBuf),
+    /// A SQLite database file.
+    Sqlite(PathBuf),
+}
+
+/// Signed links that grant access to unpublished or private content.
+#[derive(Clone, Debug)]
+pub struct LinkConfig {
+    /// `None` generates a key at startup, so links stop working on restart.
+    pub secret: Option<String>,
+    pub preview_ttl: Duration,
+    /// Also how long a project stays unlocked after its passphrase is entered.
+    pub invite_ttl: Duration,
+}
+
+/// Files uploaded through the admin media library, served under `/media`.
+#[derive(Clone, Debug)]
+pub struct MediaConfig {
+    pub dir: PathBuf,
+    pub max_upload_bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthConfig {
+    /// `None` when no admin credentials are configured, which disables login entirely.
+    pub credentials: Option<AdminCredentials>,
+    pub session_ttl: Duration,
+    pub secure_cookies: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct AdminCredentials {
+    pub username: String,
+    pub password_hash: String,
+}
+
+impl SiteConfig {
+    pub fn from_env() -> Self {
+        Self {
+            auth: AuthConfig::from_env(),
+            content: ContentBackend::from_env(),
+            links: LinkConfig::from_env(),
+            media: MediaConfig::from_env(),
+        }
+    }
+}
+
+impl ContentBackend {
+    pub fn from_env() -> Self {
+        match env::var("PORTFOLIO_CONTENT_BACKEND").as_deref() {
+            Ok("static") => ContentBackend::Static,
+            Ok("sqlite") => {
+                ContentBackend::Sqlite(env_path("PORTFOLIO_CONTENT_DB", DEFAULT_CONTENT_DB))
+            }
+            Ok("directory") | Err(_) => {
+                ContentBackend::Directory(env_path("PORTFOLIO_CONTENT_DIR", DEFAULT_CONTENT_DIR))
+            }
+            Ok(other) => panic!(
+                "unknown PORTFOLIO_CONTENT_BACKEND `{other}`; expected directory, sqlite or static"
+            ),
+        }
+    }
+}
+
+impl LinkConfig {
+    pub fn from_env() -> Self {
+        let preview_hours = env_number("PORTFOLIO_PREVIEW_TTL_HOURS", DEFAULT_PREVIEW_TTL_HOURS);
+        let invite_days = env_number("PORTFOLIO_INVITE_TTL_DAYS", DEFAULT_INVITE_TTL_DAYS);
+
+        Self {
+            secret: env::var("PORTFOLIO_LINK_SECRET")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            preview_ttl: Duration::from_secs(preview_hours * 60 * 60),
+            invite_ttl: Duration::from_secs(invite_days * 24 * 60 * 60),
+        }
+    }
+}
+
+impl MediaConfig {
+    pub fn from_env() -> Self {
+        Self {
+            dir: env_path("PORTFOLIO_MEDIA_DIR", DEFAULT_MEDIA_DIR),
+            max_upload_bytes: env_number("PORTFOLIO_MAX_UPLOAD_MB", DEFAULT_MAX_UPLOAD_MB)
+                * 1024
+                * 1024,
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn from_env() -> Self {
+        let credentials = match (
+            env::var("PORTFOLIO_ADMIN_USERNAME"),
+            env::var("PORTFOLIO_ADMIN_PASSWORD_HASH"),
+        ) {
+            (Ok(username), Ok(password_hash)) if !username.is_empty() => Some(AdminCredentials {
+                username,
+                password_hash,
+            }),
+            _ => None,
+        };
+
+        let ttl_minutes = env_number("PORTFOLIO_SESSION_TTL_MINUTES", DEFAULT_SESSION_TTL_MINUTES);
+
+        Self {
+            credentials,
+            session_ttl: Duration::from_secs(ttl_minutes * 60),
+            secure_cookies: !env_flag("PORTFOLIO_INSECURE_COOKIES"),
+        }
+    }
+}
+
+fn env_path(name: &str, default: &str) -> PathBuf {
+    env::var_os(name)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+fn env_number(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn env_flag(name: &str) -> bool {
+    matches!(env::var(name).as_deref(), Ok("1") | Ok("true"))
+}
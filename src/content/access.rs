@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+
+use super::ContentKind;
+use crate::config::LinkConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// What a signed token lets its holder see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grant {
+    /// An item that is not published yet, from an admin preview link.
+    Preview,
+    /// A private project, from an invite link or after entering its passphrase.
+    Unlock,
+}
+
+impl Grant {
+    fn as_str(self) -> &'static str {
+        match self {
+            Grant::Preview => "preview",
+            Grant::Unlock => "unlock",
+        }
+    }
+}
+
+/// Signs and checks access tokens, which open one item until they expire.
+///
+/// A token is `<expiry>.<signature>`, where the signature is an HMAC-SHA256 over the
+/// grant, the item's kind and id, and the expiry in Unix seconds, so a token for one
+/// purpose or item is useless for any other.
+#[derive(Clone)]
+pub struct AccessSigner {
+    key: Arc<[u8]>,
+    preview_ttl: Duration,
+    unlock_ttl: Duration,
+}
+
+impl AccessSigner {
+    pub fn new(config: &LinkConfig) -> Self {
+        let key = match &config.secret {
+            Some(secret) => secret.as_bytes().into(),
+            None => {
+                let mut key = [0u8; 32];
+                OsRng.fill_bytes(&mut key);
+                key.into()
+            }
+        };
+        Self {
+            key,
+            preview_ttl: config.preview_ttl,
+            unlock_ttl: config.invite_ttl,
+        }
+    }
+
+    /// How long tokens for `grant` stay valid.
+    pub fn ttl(&self, grant: Grant) -> Duration {
+        match grant {
+            Grant::Preview => self.preview_ttl,
+            Grant::Unlock => self.unlock_ttl,
+        }
+    }
+
+    pub fn sign(&self, grant: Grant, kind: ContentKind, id: &str) -> String {
+        let expires = Utc::now().timestamp() + self.ttl(grant).as_secs() as i64;
+        let signature = self.mac(grant, kind, id, expires).finalize().into_bytes();
+        format!("{expires}.{}", hex(&signature))
+    }
+
+    pub fn verify(&self, grant: Grant, kind: ContentKind, id: &str, token: &str) -> bool {
+        let Some((expires, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let (Ok(expires), Some(signature)) = (expires.parse::<i64>(), unhex(signature)) else {
+            return false;
+        };
+        expires > Utc::now().timestamp()
+            && self
+                .mac(grant, kind, id, expires)
+                .verify_slice(&signature)
+                .is_ok()
+    }
+
+    fn mac(&self, grant: Grant, kind: ContentKind, id: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}\n{}\n{id}\n{expires}", grant.as_str(), kind.as_str()).as_bytes());
+        mac
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -0,0 +1,233 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{
+    remove, upsert, validate_photos, validate_record, AuditEntry, ContentError, ContentKind,
+    ContentSource, MediaItem, Revision, BLOGS_FILE, PHOTOS_FILE, PROJECTS_FILE,
+};
+use crate::data::{all_blogs, all_projects, get_photos, Blog, Photo, Project};
+
+#[derive(Clone, Debug, Default)]
+struct Snapshot {
+    projects: Vec<Project>,
+    blogs: Vec<Blog>,
+    photos: Vec<Photo>,
+    media: Vec<MediaItem>,
+}
+
+/// Revisions and audit entries are appended to these as one JSON document per line.
+const REVISIONS_FILE: &str = "revisions.jsonl";
+const AUDIT_FILE: &str = "audit.jsonl";
+/// Metadata of the media library; the files themselves live in the media directory.
+const MEDIA_FILE: &str = "media.json";
+
+/// Content kept in memory and persisted as JSON files in a directory.
+///
+/// Files that do not exist yet are seeded from the built-in data, so a fresh
+/// deployment starts out with the same content as the static site. History is only
+/// read when asked for, so it stays on disk.
+pub struct DirectoryContent {
+    dir: PathBuf,
+    snapshot: RwLock<Snapshot>,
+    /// Serializes appends, so revision numbers are handed out once.
+    history: Mutex<()>,
+}
+
+impl DirectoryContent {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ContentError> {
+        let dir = dir.into();
+        let snapshot = Snapshot {
+            projects: read_or(&dir.join(PROJECTS_FILE), all_projects)?,
+            blogs: read_or(&dir.join(BLOGS_FILE), all_blogs)?,
+            photos: read_or(&dir.join(PHOTOS_FILE), get_photos)?,
+            media: read_or(&dir.join(MEDIA_FILE), Vec::new)?,
+        };
+        Ok(Self {
+            dir,
+            snapshot: RwLock::new(snapshot),
+            history: Mutex::new(()),
+        })
+    }
+
+    /// Writes a file atomically so a crash never leaves half-written content behind.
+    fn persist<T: Serialize>(&self, file: &str, value: &T) -> Result<(), ContentError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(file);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    fn append<T: Serialize>(&self, file: &str, value: &T) -> Result<(), ContentError> {
+        fs::create_dir_all(&self.dir)?;
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        let mut out = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(file))?;
+        out.write_all(&line)?;
+        Ok(())
+    }
+
+    /// Reads a log file, oldest entry first.
+    fn read_log<T: DeserializeOwned>(&self, file: &str) -> Result<Vec<T>, ContentError> {
+        let text = match fs::read_to_string(self.dir.join(file)) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+}
+
+impl ContentSource for DirectoryContent {
+    fn projects(&self) -> Result<Vec<Project>, ContentError> {
+        Ok(self.snapshot.read().unwrap().projects.clone())
+    }
+
+    fn blogs(&self) -> Result<Vec<Blog>, ContentError> {
+        Ok(self.snapshot.read().unwrap().blogs.clone())
+    }
+
+    fn photos(&self) -> Result<Vec<Photo>, ContentError> {
+        Ok(self.snapshot.read().unwrap().photos.clone())
+    }
+
+    fn project(&self, id: &str) -> Result<Option<Project>, ContentError> {
+        Ok(self
+            .snapshot
+            .read()
+            .unwrap()
+            .projects
+            .iter()
+            .find(|p| &*p.id == id)
+            .cloned())
+    }
+
+    fn blog(&self, id: &str) -> Result<Option<Blog>, ContentError> {
+        Ok(self
+            .snapshot
+            .read()
+            .unwrap()
+            .blogs
+            .iter()
+            .find(|b| &*b.id == id)
+            .cloned())
+    }
+
+    fn save_project(
+        &self,
+        original_id: Option<&str>,
+        project: Project,
+    ) -> Result<(), ContentError> {
+        validate_record("project", &project.id, &project.title)?;
+        let mut snapshot = self.snapshot.write().unwrap();
+        upsert(&mut snapshot.projects, original_id, project, |p| &*p.id)?;
+        self.persist(PROJECTS_FILE, &snapshot.projects)
+    }
+
+    fn delete_project(&self, id: &str) -> Result<(), ContentError> {
+        let mut snapshot = self.snapshot.write().unwrap();
+        remove(&mut snapshot.projects, id, |p| &*p.id)?;
+        self.persist(PROJECTS_FILE, &snapshot.projects)
+    }
+
+    fn save_blog(&self, original_id: Option<&str>, blog: Blog) -> Result<(), ContentError> {
+        validate_record("blog post", &blog.id, &blog.title)?;
+        let mut snapshot = self.snapshot.write().unwrap();
+        upsert(&mut snapshot.blogs, original_id, blog, |b| &*b.id)?;
+        self.persist(BLOGS_FILE, &snapshot.blogs)
+    }
+
+    fn delete_blog(&self, id: &str) -> Result<(), ContentError> {
+        let mut snapshot = self.snapshot.write().unwrap();
+        remove(&mut snapshot.blogs, id, |b| &*b.id)?;
+        self.persist(BLOGS_FILE, &snapshot.blogs)
+    }
+
+    fn save_photos(&self, photos: Vec<Photo>) -> Result<(), ContentError> {
+        validate_photos(&photos)?;
+        let mut snapshot = self.snapshot.write().unwrap();
+        snapshot.photos = photos;
+        self.persist(PHOTOS_FILE, &snapshot.photos)
+    }
+
+    fn add_revision(&self, mut revision: Revision) -> Result<u64, ContentError> {
+        let _guard = self.history.lock().unwrap();
+        let last = self
+            .read_log::<Revision>(REVISIONS_FILE)?
+            .last()
+            .map_or(0, |r| r.number);
+        revision.number = last + 1;
+        self.append(REVISIONS_FILE, &revision)?;
+        Ok(revision.number)
+    }
+
+    fn revisions(&self, kind: ContentKind, id: &str) -> Result<Vec<Revision>, ContentError> {
+        let mut revisions = self.read_log::<Revision>(REVISIONS_FILE)?;
+        revisions.retain(|r| r.kind == kind && &*r.id == id);
+        revisions.reverse();
+        Ok(revisions)
+    }
+
+    fn revision(&self, number: u64) -> Result<Option<Revision>, ContentError> {
+        Ok(self
+            .read_log::<Revision>(REVISIONS_FILE)?
+            .into_iter()
+            .find(|r| r.number == number))
+    }
+
+    fn append_audit(&self, entry: AuditEntry) -> Result<(), ContentError> {
+        let _guard = self.history.lock().unwrap();
+        self.append(AUDIT_FILE, &entry)
+    }
+
+    fn audit_log(&self, limit: usize) -> Result<Vec<AuditEntry>, ContentError> {
+        let entries = self.read_log::<AuditEntry>(AUDIT_FILE)?;
+        Ok(entries.into_iter().rev().take(limit).collect())
+    }
+
+    fn media(&self) -> Result<Vec<MediaItem>, ContentError> {
+        Ok(self.snapshot.read().unwrap().media.clone())
+    }
+
+    fn save_media_item(&self, item: MediaItem) -> Result<(), ContentError> {
+        let mut snapshot = self.snapshot.write().unwrap();
+        match snapshot
+            .media
+            .iter_mut()
+            .find(|existing| existing.id == item.id)
+        {
+            Some(existing) => *existing = item,
+            None => snapshot.media.insert(0, item),
+        }
+        self.persist(MEDIA_FILE, &snapshot.media)
+    }
+
+    fn delete_media_item(&self, id: &str) -> Result<(), ContentError> {
+        let mut snapshot = self.snapshot.write().unwrap();
+        remove(&mut snapshot.media, id, |m| &*m.id)?;
+        self.persist(MEDIA_FILE, &snapshot.media)
+    }
+}
+
+fn read_or<T: DeserializeOwned>(
+    path: &Path,
+    default: impl FnOnce() -> T,
+) -> Result<T, ContentError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(default()),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,394 @@
+//! Revision history for projects and blog posts, and the audit log of admin actions.
+//!
+//! Every save, delete and rollback made through the admin area stores a [`Revision`]
+//! holding the whole record as it was afterwards, so any earlier state can be compared
+//! with another or restored. Revision numbers increase across all content.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ContentKind, ShareLink};
+use crate::data::{Blog, Poster, Project, Status, Text};
+
+/// Author of the revision recorded for an item's state before its first edit.
+pub const ORIGINAL_AUTHOR: &str = "(original)";
+
+/// A project or blog post as stored in a revision.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Record {
+    Project(Project),
+    Blog(Blog),
+}
+
+impl Record {
+    pub fn kind(&self) -> ContentKind {
+        match self {
+            Record::Project(_) => ContentKind::Project,
+            Record::Blog(_) => ContentKind::Blog,
+        }
+    }
+
+    pub fn id(&self) -> &Text {
+        match self {
+            Record::Project(project) => &project.id,
+            Record::Blog(blog) => &blog.id,
+        }
+    }
+
+    pub fn without_secrets(self) -> Self {
+        match self {
+            Record::Project(project) => Record::Project(project.without_secrets()),
+            blog => blog,
+        }
+    }
+
+    /// The record as `Field: value` lines for diffing. Long text and lists continue on
+    /// indented lines so a change to one paragraph or item shows up on its own.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Lines::default();
+        match self {
+            Record::Project(p) => {
+                lines.field("Id", &p.id);
+                lines.field("Title", &p.title);
+                lines.field("Subtitle", &p.subtitle);
+                lines.field("Tag", &p.tag);
+                lines.field("Status", &status_text(p.status));
+                lines.field(
+                    "Visibility",
+                    if p.is_private() { "private" } else { "public" },
+                );
+                lines.field("Description", &p.description);
+                lines.field("Overview", &p.overview);
+                lines.field("My Role", &p.role);
+                lines.list(
+                    "Technologies",
+                    p.technologies
+                        .as_deref()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|t| t.to_string()),
+                );
+                lines.optional("Live Site", p.live_link.as_deref());
+                lines.optional("Code", p.code_link.as_deref());
+                lines.optional("Paper", p.paper_link.as_deref());
+                lines.list(
+                    "Posters",
+                    p.posters
+                        .as_deref()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(poster_line),
+                );
+                lines.list(
+                    "Photos",
+                    p.photos
+                        .as_deref()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|p| format!("{} <{}>", p.caption, p.url)),
+                );
+                let blocks = p
+                    .blocks
+                    .as_ref()
+                    .map(|blocks| serde_json::to_string_pretty(blocks).unwrap_or_default());
+                lines.optional("Blocks", blocks.as_deref());
+                lines.optional("Demo", p.demo.as_deref());
+            }
+            Record::Blog(b) => {
+                lines.field("Id", &b.id);
+                lines.field("Title", &b.title);
+                lines.field("Subtitle", &b.subtitle);
+                lines.field("Tag", &b.tag);
+                lines.field("Status", &status_text(b.status));
+                lines.field("Description", &b.description);
+                lines.field("Overview", &b.overview);
+                lines.optional("Link", b.live_link.as_deref());
+            }
+        }
+        lines.0
+    }
+}
+
+#[derive(Default)]
+struct Lines(Vec<String>);
+
+impl Lines {
+    fn field(&mut self, label: &str, value: &str) {
+        let mut rows = value.lines();
+        self.0
+            .push(format!("{label}: {}", rows.next().unwrap_or_default()));
+        self.0.extend(rows.map(|row| format!("    {row}")));
+    }
+
+    fn optional(&mut self, label: &str, value: Option<&str>) {
+        self.field(label, value.unwrap_or("—"));
+    }
+
+    fn list(&mut self, label: &str, items: impl Iterator<Item = String>) {
+        self.0.push(format!("{label}:"));
+        self.0.extend(items.map(|item| format!("  - {item}")));
+    }
+}
+
+fn poster_line(poster: &Poster) -> String {
+    let mut line = format!("{} <{}>", poster.name, poster.url);
+    if let Some(preview) = &poster.preview {
+        line.push_str(&format!(", preview <{preview}>"));
+    }
+    if let Some(pages) = poster.pages {
+        line.push_str(&format!(", {pages} page(s)"));
+    }
+    line
+}
+
+fn status_text(status: Status) -> String {
+    match status {
+        Status::Scheduled { publish_at } => {
+            format!("scheduled for {}", publish_at.format("%Y-%m-%d %H:%M UTC"))
+        }
+        other => other.label().to_lowercase(),
+    }
+}
+
+/// One change to a project or blog post.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    /// Assigned by the content source when the revision is stored.
+    pub number: u64,
+    pub kind: ContentKind,
+    /// The item's id after the change, or the deleted item's id.
+    pub id: Text,
+    /// The id the item had before the change. Differs from `id` for a rename and is `None`
+    /// where the item's history starts: when it was created, or its original state.
+    pub previous_id: Option<Text>,
+    pub author: Text,
+    pub at: DateTime<Utc>,
+    /// Set when this change restored an earlier revision.
+    pub restored_from: Option<u64>,
+    /// The item after the change, or `None` if it was deleted.
+    pub record: Option<Record>,
+}
+
+impl Revision {
+    pub fn summary(&self) -> String {
+        if self.record.is_none() {
+            return "Deleted".to_string();
+        }
+        if let Some(number) = self.restored_from {
+            return format!("Rolled back to #{number}");
+        }
+        match &self.previous_id {
+            None if &*self.author == ORIGINAL_AUTHOR => "Original version".to_string(),
+            None => "Created".to_string(),
+            Some(previous) if *previous != self.id => format!("Renamed from `{previous}`"),
+            Some(_) => "Edited".to_string(),
+        }
+    }
+}
+
+/// Something an admin did, as kept in the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AuditAction {
+    SignedIn,
+    SignInFailed,
+    SignedOut,
+    Saved {
+        kind: ContentKind,
+        id: Text,
+        revision: u64,
+    },
+    Deleted {
+        kind: ContentKind,
+        id: Text,
+        revision: u64,
+    },
+    RolledBack {
+        kind: ContentKind,
+        id: Text,
+        to: u64,
+        revision: u64,
+    },
+    SavedPhotos {
+        count: usize,
+    },
+    UploadedMedia {
+        id: Text,
+    },
+    EditedMedia {
+        id: Text,
+    },
+    DeletedMedia {
+        id: Text,
+    },
+    CreatedLink {
+        link: ShareLink,
+        kind: ContentKind,
+        id: Text,
+    },
+}
+
+impl AuditAction {
+    pub fn describe(&self) -> String {
+        match self {
+            AuditAction::SignedIn => "Signed in".to_string(),
+            AuditAction::SignInFailed => "Failed sign-in attempt".to_string(),
+            AuditAction::SignedOut => "Signed out".to_string(),
+            AuditAction::Saved { kind, id, revision } => {
+                format!("Saved {} `{id}` (#{revision})", kind.as_str())
+            }
+            AuditAction::Deleted { kind, id, revision } => {
+                format!("Deleted {} `{id}` (#{revision})", kind.as_str())
+            }
+            AuditAction::RolledBack {
+                kind,
+                id,
+                to,
+                revision,
+            } => {
+                format!(
+                    "Rolled back {} `{id}` to #{to} (#{revision})",
+                    kind.as_str()
+                )
+            }
+            AuditAction::SavedPhotos { count } => {
+                format!("Saved the photo gallery ({count} photos)")
+            }
+            AuditAction::UploadedMedia { id } => format!("Uploaded `{id}`"),
+            AuditAction::EditedMedia { id } => format!("Edited the caption or alt text of `{id}`"),
+            AuditAction::DeletedMedia { id } => format!("Deleted `{id}` from the media library"),
+            AuditAction::CreatedLink { link, kind, id } => {
+                let what = match link {
+                    ShareLink::Preview => "preview",
+                    ShareLink::Invite => "invite",
+                };
+                format!("Created a {what} link for {} `{id}`", kind.as_str())
+            }
+        }
+    }
+
+    /// The item the action concerns, if any.
+    pub fn target(&self) -> Option<(ContentKind, &Text)> {
+        match self {
+            AuditAction::Saved { kind, id, .. }
+            | AuditAction::Deleted { kind, id, .. }
+            | AuditAction::RolledBack { kind, id, .. }
+            | AuditAction::CreatedLink { kind, id, .. } => Some((*kind, id)),
+            _ => None,
+        }
+    }
+}
+
+/// An entry in the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    /// The signed-in admin, or the username tried for a failed sign-in.
+    pub actor: Text,
+    pub action: AuditAction,
+}
+
+#[cfg(feature = "ssr")]
+impl super::Content {
+    /// Every revision of the item now called `id`, newest first, following renames back
+    /// to where its history starts.
+    pub fn history(
+        &self,
+        kind: ContentKind,
+        id: &str,
+    ) -> Result<Vec<Revision>, super::ContentError> {
+        let mut history = Vec::new();
+        let mut id = Text::from(id);
+        let mut before = u64::MAX;
+        loop {
+            let mut renamed = None;
+            for revision in self
+                .revisions(kind, &id)?
+                .into_iter()
+                .filter(|r| r.number < before)
+            {
+                let previous_id = revision.previous_id.clone();
+                let number = revision.number;
+                history.push(revision);
+                match previous_id {
+                    None => return Ok(history),
+                    Some(previous) if previous != id => {
+                        renamed = Some((previous, number));
+                        break;
+                    }
+                    Some(_) => {}
+                }
+            }
+            let Some((previous, number)) = renamed else {
+                return Ok(history);
+            };
+            (id, before) = (previous, number);
+        }
+    }
+
+    /// Stores the revision for a change from `previous` to `current` (either is `None`
+    /// for a creation or deletion) and returns its number. An item edited for the first
+    /// time gets its previous state stored too, so the edit can be rolled back.
+    pub fn record_change(
+        &self,
+        author: &str,
+        previous: Option<Record>,
+        current: Option<Record>,
+        restored_from: Option<u64>,
+    ) -> Result<u64, super::ContentError> {
+        let Some(kind) = current.as_ref().or(previous.as_ref()).map(Record::kind) else {
+            return Err(super::ContentError::Invalid(
+                "A change needs a record.".to_string(),
+            ));
+        };
+        let previous_id = match &previous {
+            Some(previous) => {
+                let id = previous.id().clone();
+                if self.history(kind, &id)?.is_empty() {
+                    self.add_revision(Revision {
+                        number: 0,
+                        kind,
+                        id: id.clone(),
+                        previous_id: None,
+                        author: ORIGINAL_AUTHOR.into(),
+                        at: Utc::now(),
+                        restored_from: None,
+                        record: Some(previous.clone()),
+                    })?;
+                }
+                Some(id)
+            }
+            // Restoring a deleted item continues its history rather than starting anew.
+            None if restored_from.is_some() => current.as_ref().map(|c| c.id().clone()),
+            None => None,
+        };
+        let id = current
+            .as_ref()
+            .or(previous.as_ref())
+            .map(|r| r.id().clone())
+            .unwrap_or_default();
+        self.add_revision(Revision {
+            number: 0,
+            kind,
+            id,
+            previous_id,
+            author: author.into(),
+            at: Utc::now(),
+            restored_from,
+            record: current,
+        })
+    }
+
+    /// Adds an entry to the audit log. Failures are logged rather than returned, so
+    /// auditing never blocks the action itself.
+    pub fn audit(&self, actor: &str, action: AuditAction) {
+        let entry = AuditEntry {
+            at: Utc::now(),
+            actor: actor.into(),
+            action,
+        };
+        match self.append_audit(entry) {
+            Ok(()) | Err(super::ContentError::ReadOnly) => {}
+            Err(err) => leptos::logging::error!("could not write the audit log: {err}"),
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Consistency checks over content, run by `portfolio lint` and the test suite.
+//!
+//! Problems are collected rather than stopping at the first, each with the path of the
+//! offending field (`projects[4] (spotwelder).photos[1].url`), so one run shows
+//! everything that needs fixing.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use super::MEDIA_URL_DIR;
+use crate::data::{Block, Blog, Chart, Photo, Project};
+use crate::demos::{self, DEMOS};
+
+/// Where content's relative URLs point to on disk.
+#[derive(Clone, Debug)]
+pub struct Assets {
+    /// The static assets directory, `public/` in this repository.
+    pub public_dir: PathBuf,
+    /// Uploads from the media library, for URLs under [`MEDIA_URL_DIR`].
+    pub media_dir: PathBuf,
+}
+
+impl Assets {
+    fn file_for(&self, url: &str) -> PathBuf {
+        let path = url.trim_start_matches('/');
+        match path
+            .strip_prefix(MEDIA_URL_DIR)
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            Some(file) => self.media_dir.join(file),
+            None => self.public_dir.join(path),
+        }
+    }
+}
+
+/// Something wrong with one field of the content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Checks ids, images and links of all the given content and returns every problem found.
+pub fn lint_content(
+    projects: &[Project],
+    blogs: &[Blog],
+    photos: &[Photo],
+    assets: &Assets,
+) -> Vec<Problem> {
+    let mut lint = Lint {
+        assets,
+        problems: Vec::new(),
+    };
+
+    let mut project_ids = HashMap::new();
+    for (index, project) in projects.iter().enumerate() {
+        let at = format!("projects[{index}] ({})", project.id);
+        lint.id(&at, &project.id, index, &mut project_ids, "projects");
+        lint.required(&format!("{at}.title"), &project.title);
+        for (field, link) in [
+            ("live_link", &project.live_link),
+            ("code_link", &project.code_link),
+            ("paper_link", &project.paper_link),
+        ] {
+            if let Some(link) = link {
+                lint.link(&format!("{at}.{field}"), link);
+            }
+        }
+        for (i, poster) in project
+            .posters
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            lint.required(&format!("{at}.posters[{i}].name"), &poster.name);
+            lint.link(&format!("{at}.posters[{i}].url"), &poster.url);
+            if let Some(preview) = &poster.preview {
+                lint.local_file(&format!("{at}.posters[{i}].preview"), preview);
+            }
+            if poster.pages == Some(0) {
+                lint.report(&format!("{at}.posters[{i}].pages"), "must be at least 1");
+            }
+        }
+        for (i, photo) in project
+            .photos
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            lint.photo(&format!("{at}.photos[{i}]"), photo);
+        }
+        for (i, block) in project
+            .blocks
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            lint.block(&format!("{at}.blocks[{i}]"), block);
+        }
+        if let Some(demo) = project
+            .demo
+            .as_deref()
+            .filter(|demo| demos::find(demo).is_none())
+        {
+            let known: Vec<_> = DEMOS.iter().map(|demo| demo.key).collect();
+            lint.report(
+                &format!("{at}.demo"),
+                format!(
+                    "`{demo}` is not a known demo (expected one of {})",
+                    known.join(", ")
+                ),
+            );
+        }
+    }
+
+    let mut blog_ids = HashMap::new();
+    for (index, blog) in blogs.iter().enumerate() {
+        let at = format!("blogs[{index}] ({})", blog.id);
+        lint.id(&at, &blog.id, index, &mut blog_ids, "blogs");
+        lint.required(&format!("{at}.title"), &blog.title);
+        if let Some(link) = &blog.live_link {
+            lint.link(&format!("{at}.live_link"), link);
+        }
+    }
+
+    for (index, photo) in photos.iter().enumerate() {
+        lint.photo(&format!("photos[{index}]"), photo);
+    }
+
+    lint.problems
+}
+
+struct Lint<'a> {
+    assets: &'a Assets,
+    problems: Vec<Problem>,
+}
+
+impl Lint<'_> {
+    fn report(&mut self, location: &str, message: impl Into<String>) {
+        self.problems.push(Problem {
+            location: location.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Ids end up in URLs, so they must be unique within their kind and path-safe.
+    fn id<'id>(
+        &mut self,
+        at: &str,
+        id: &'id str,
+        index: usize,
+        seen: &mut HashMap<&'id str, usize>,
+        list: &str,
+    ) {
+        let location = format!("{at}.id");
+        if id.is_empty() {
+            self.report(&location, "is empty");
+        } else if id.chars().any(|c| c.is_whitespace() || "/?#%".contains(c)) {
+            self.report(
+                &location,
+                format!("`{id}` contains characters that are not allowed in a URL path segment"),
+            );
+        }
+        match seen.get(id) {
+            Some(first) => self.report(
+                &location,
+                format!("`{id}` is already used by {list}[{first}]"),
+            ),
+            None => {
+                seen.insert(id, index);
+            }
+        }
+    }
+
+    fn required(&mut self, location: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.report(location, "is empty");
+        }
+    }
+
+    fn photo(&mut self, at: &str, photo: &Photo) {
+        self.local_file(&format!("{at}.url"), &photo.url);
+        self.required(&format!("{at}.caption"), &photo.caption);
+        self.required(&format!("{at}.alt"), &photo.alt);
+    }
+
+    fn block(&mut self, at: &str, block: &Block) {
+        match block {
+            Block::RichText { body, .. } | Block::Callout { body, .. } => {
+                self.required(&format!("{at}.body"), body)
+            }
+            Block::Image(photo) => self.photo(at, photo),
+            Block::Gallery { photos, .. } => {
+                if photos.is_empty() {
+                    self.report(&format!("{at}.photos"), "is empty");
+                }
+                for (i, photo) in photos.iter().enumerate() {
+                    self.photo(&format!("{at}.photos[{i}]"), photo);
+                }
+            }
+            Block::Code { code, .. } => self.required(&format!("{at}.code"), code),
+            Block::Quote { text, .. } => self.required(&format!("{at}.text"), text),
+            Block::Metrics { items, .. } => {
+                if items.is_empty() {
+                    self.report(&format!("{at}.items"), "is empty");
+                }
+                for (i, metric) in items.iter().enumerate() {
+                    self.required(&format!("{at}.items[{i}].value"), &metric.value);
+                    self.required(&format!("{at}.items[{i}].label"), &metric.label);
+                }
+            }
+            Block::Video { url, .. } => self.link(&format!("{at}.url"), url),
+            Block::Chart(chart) => self.chart(at, chart),
+        }
+    }
+
+    fn chart(&mut self, at: &str, chart: &Chart) {
+        if chart.series.is_empty() {
+            self.report(&format!("{at}.series"), "is empty");
+        }
+        for (i, series) in chart.series.iter().enumerate() {
+            let at = format!("{at}.series[{i}]");
+            if chart.series.len() > 1 {
+                self.required(&format!("{at}.name"), &series.name);
+            }
+            if chart.is_categorical() {
+                if series.values.len() != chart.categories.len() {
+                    let (values, categories) = (series.values.len(), chart.categories.len());
+                    self.report(
+                        &format!("{at}.values"),
+                        format!("has {values} value(s) for {categories} categories"),
+                    );
+                }
+            } else if series.points.is_empty() {
+                self.report(&format!("{at}.points"), "is empty");
+            }
+        }
+    }
+
+    /// Links are either absolute http(s) URLs or paths to files the site serves.
+    fn link(&mut self, location: &str, link: &str) {
+        if link.contains("://") {
+            match check_url(link) {
+                Ok(()) => {}
+                Err(reason) => {
+                    self.report(location, format!("`{link}` is not a valid URL: {reason}"))
+                }
+            }
+        } else {
+            self.local_file(location, link);
+        }
+    }
+
+    fn local_file(&mut self, location: &str, url: &str) {
+        if url.is_empty() {
+            self.report(location, "is empty");
+            return;
+        }
+        if url.contains("://") {
+            self.report(
+                location,
+                format!("`{url}` must be a file served by the site, not an external URL"),
+            );
+            return;
+        }
+        let path = self.assets.file_for(url);
+        if !path.is_file() {
+            self.report(
+                location,
+                format!("`{url}` does not exist (looked for {})", path.display()),
+            );
+        }
+    }
+}
+
+fn check_url(link: &str) -> Result<(), String> {
+    if link.chars().any(char::is_whitespace) {
+        return Err("contains whitespace".to_string());
+    }
+    let url = url::Url::parse(link).map_err(|err| err.to_string())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "the scheme must be http or https, not {}",
+            url.scheme()
+        ));
+    }
+    if !url
+        .host_str()
+        .is_some_and(|host| host.contains('.') || host == "localhost")
+    {
+        return Err("the host is missing or incomplete".to_string());
+    }
+    Ok(())
+}
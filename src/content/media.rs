@@ -0,0 +1,352 @@
+//! The media library: images and PDFs uploaded through the admin area.
+//!
+//! Uploaded files live in [`crate::config::MediaConfig::dir`] and are served under
+//! [`MEDIA_URL_DIR`]; their metadata is kept by the [`ContentSource`](super::ContentSource)
+//! alongside the rest of the content. Images are shrunk to [`MAX_IMAGE_SIDE`] and
+//! re-encoded on upload, so what is served never depends on what a camera produced.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ContentKind;
+use crate::data::{Block, Blog, Photo, Project, Text};
+
+/// Where media files are served, relative to the site's base path.
+pub const MEDIA_URL_DIR: &str = "media";
+
+/// Uploaded images are scaled down to fit this many pixels on their longest side.
+pub const MAX_IMAGE_SIDE: u32 = 2400;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Pdf,
+}
+
+/// One uploaded file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MediaItem {
+    /// The stored file name, unique within the library.
+    pub id: Text,
+    pub kind: MediaKind,
+    /// The name of the file as it was uploaded.
+    pub original_name: Text,
+    pub size: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// File name of a smaller copy, for images.
+    pub thumbnail: Option<Text>,
+    pub caption: Text,
+    pub alt: Text,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl MediaItem {
+    /// The URL content should use to reference this file.
+    pub fn url(&self) -> String {
+        format!("{MEDIA_URL_DIR}/{}", self.id)
+    }
+
+    pub fn thumbnail_url(&self) -> Option<String> {
+        self.thumbnail
+            .as_ref()
+            .map(|file| format!("{MEDIA_URL_DIR}/{file}"))
+    }
+
+    /// Whether `url`, as written in content, points at this file.
+    pub fn is_referenced_by(&self, url: &str) -> bool {
+        url.trim_start_matches('/') == self.url()
+    }
+}
+
+/// Somewhere a media file is referenced from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MediaUse {
+    Item {
+        kind: ContentKind,
+        id: Text,
+        title: Text,
+    },
+    Gallery,
+}
+
+/// A library entry as the admin area shows it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MediaEntry {
+    pub item: MediaItem,
+    pub uses: Vec<MediaUse>,
+}
+
+/// Every place in `projects`, `blogs` and the `gallery` that references `item`.
+pub fn find_uses(
+    item: &MediaItem,
+    projects: &[Project],
+    blogs: &[Blog],
+    gallery: &[Photo],
+) -> Vec<MediaUse> {
+    let mut uses = Vec::new();
+    for project in projects {
+        let photos = project
+            .photos
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|p| &*p.url);
+        let posters = project.posters.as_deref().unwrap_or_default().iter();
+        let posters = posters.flat_map(|p| std::iter::once(&*p.url).chain(p.preview.as_deref()));
+        let blocks = project
+            .blocks
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|block| {
+                let video = match block {
+                    Block::Video { url, .. } => Some(&**url),
+                    _ => None,
+                };
+                block.photos().iter().map(|p| &*p.url).chain(video)
+            });
+        let links = [&project.paper_link, &project.live_link, &project.code_link];
+        let mut urls = photos
+            .chain(posters)
+            .chain(blocks)
+            .chain(links.into_iter().flatten().map(|l| &**l));
+        if urls.any(|url| item.is_referenced_by(url)) {
+            uses.push(MediaUse::Item {
+                kind: ContentKind::Project,
+                id: project.id.clone(),
+                title: project.title.clone(),
+            });
+        }
+    }
+    for blog in blogs {
+        if blog
+            .live_link
+            .as_deref()
+            .is_some_and(|url| item.is_referenced_by(url))
+        {
+            uses.push(MediaUse::Item {
+                kind: ContentKind::Blog,
+                id: blog.id.clone(),
+                title: blog.title.clone(),
+            });
+        }
+    }
+    if gallery
+        .iter()
+        .any(|photo| item.is_referenced_by(&photo.url))
+    {
+        uses.push(MediaUse::Gallery);
+    }
+    uses
+}
+
+/// Fills in empty captions and alt text of photos that show library images, from the
+/// library's own.
+pub fn apply_media_text(photos: &mut [Photo], library: &[MediaItem]) {
+    for photo in photos {
+        let Some(item) = library
+            .iter()
+            .find(|item| item.is_referenced_by(&photo.url))
+        else {
+            continue;
+        };
+        if photo.caption.is_empty() {
+            photo.caption = item.caption.clone();
+        }
+        if photo.alt.is_empty() {
+            photo.alt = item.alt.clone();
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use store::MediaStore;
+
+#[cfg(feature = "ssr")]
+mod store {
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    use chrono::Utc;
+    use image::codecs::jpeg::JpegEncoder;
+    use image::imageops::FilterType;
+    use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+    use rand::Rng;
+
+    use super::{MediaItem, MediaKind, MAX_IMAGE_SIDE};
+    use crate::config::MediaConfig;
+    use crate::content::ContentError;
+
+    /// Longest side of the thumbnails shown in the library.
+    const THUMBNAIL_SIDE: u32 = 480;
+    const JPEG_QUALITY: u8 = 85;
+    /// Refuse to decode anything larger, whatever its file size.
+    const MAX_DECODED_SIDE: u32 = 16_000;
+
+    /// Processes uploads and keeps the files of the media library on disk.
+    #[derive(Clone, Debug)]
+    pub struct MediaStore {
+        dir: PathBuf,
+        max_upload_bytes: u64,
+    }
+
+    impl MediaStore {
+        pub fn new(config: &MediaConfig) -> Self {
+            Self {
+                dir: config.dir.clone(),
+                max_upload_bytes: config.max_upload_bytes,
+            }
+        }
+
+        pub fn dir(&self) -> &PathBuf {
+            &self.dir
+        }
+
+        pub fn max_upload_bytes(&self) -> u64 {
+            self.max_upload_bytes
+        }
+
+        /// Checks, converts and stores an uploaded file, returning its library entry.
+        /// Images are decoded and re-encoded, so nothing but pixels survives: JPEG for
+        /// opaque images, PNG where there is transparency. PDFs are stored as they are.
+        pub fn store(&self, original_name: &str, bytes: &[u8]) -> Result<MediaItem, ContentError> {
+            fs::create_dir_all(&self.dir)?;
+            let stem = file_stem(original_name);
+
+            let mut item = MediaItem {
+                id: "".into(),
+                kind: MediaKind::Pdf,
+                original_name: original_name.into(),
+                size: 0,
+                width: None,
+                height: None,
+                thumbnail: None,
+                caption: "".into(),
+                alt: "".into(),
+                uploaded_at: Utc::now(),
+            };
+
+            if bytes.starts_with(b"%PDF-") {
+                let id = self.unused_name(&stem, "pdf");
+                fs::write(self.dir.join(&id), bytes)?;
+                item.id = id.into();
+                item.size = bytes.len() as u64;
+                return Ok(item);
+            }
+
+            let image = decode(bytes)?;
+            let image = fit(image, MAX_IMAGE_SIDE);
+            let extension = if image.color().has_alpha() {
+                "png"
+            } else {
+                "jpg"
+            };
+            let id = self.unused_name(&stem, extension);
+            let encoded = encode(&image, extension)?;
+            let thumbnail = format!(
+                "{}.thumb.{extension}",
+                id.trim_end_matches(&format!(".{extension}"))
+            );
+            let thumbnail_bytes = encode(&fit(image.clone(), THUMBNAIL_SIDE), extension)?;
+
+            fs::write(self.dir.join(&id), &encoded)?;
+            fs::write(self.dir.join(&thumbnail), thumbnail_bytes)?;
+            item.id = id.into();
+            item.kind = MediaKind::Image;
+            item.size = encoded.len() as u64;
+            item.width = Some(image.width());
+            item.height = Some(image.height());
+            item.thumbnail = Some(thumbnail.into());
+            Ok(item)
+        }
+
+        /// Removes an item's files. Missing files are not an error.
+        pub fn remove(&self, item: &MediaItem) -> Result<(), ContentError> {
+            for file in std::iter::once(&item.id).chain(&item.thumbnail) {
+                match fs::remove_file(self.dir.join(&**file)) {
+                    Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                        return Err(err.into())
+                    }
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+
+        fn unused_name(&self, stem: &str, extension: &str) -> String {
+            loop {
+                let suffix: u32 = rand::thread_rng().gen_range(0x1000_0000..=u32::MAX);
+                let name = format!("{stem}-{suffix:08x}.{extension}");
+                if !self.dir.join(&name).exists() {
+                    return name;
+                }
+            }
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<DynamicImage, ContentError> {
+        let unsupported = || {
+            ContentError::Invalid(
+                "Only JPEG, PNG, WebP and GIF images and PDF files can be uploaded.".to_string(),
+            )
+        };
+        let mut reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+        match reader.format() {
+            Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Gif) => {}
+            _ => return Err(unsupported()),
+        }
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(MAX_DECODED_SIDE);
+        limits.max_image_height = Some(MAX_DECODED_SIDE);
+        reader.limits(limits);
+        reader
+            .decode()
+            .map_err(|err| ContentError::Invalid(format!("Could not read the image: {err}")))
+    }
+
+    /// Scales `image` down to fit `side` pixels, leaving smaller images alone.
+    fn fit(image: DynamicImage, side: u32) -> DynamicImage {
+        if image.width().max(image.height()) > side {
+            image.resize(side, side, FilterType::Lanczos3)
+        } else {
+            image
+        }
+    }
+
+    fn encode(image: &DynamicImage, extension: &str) -> Result<Vec<u8>, ContentError> {
+        let mut out = Vec::new();
+        let result = if extension == "png" {
+            image.write_to(&mut Cursor::new(&mut out), ImageFormat::Png)
+        } else {
+            image
+                .to_rgb8()
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY))
+        };
+        result
+            .map_err(|err| ContentError::Invalid(format!("Could not convert the image: {err}")))?;
+        Ok(out)
+    }
+
+    /// A safe, readable file name base from whatever the browser sent.
+    fn file_stem(original_name: &str) -> String {
+        let stem = original_name.rsplit(['/', '\\']).next().unwrap_or_default();
+        let stem = stem.rsplit_once('.').map_or(stem, |(stem, _)| stem);
+        let mut slug = String::new();
+        for c in stem.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug: String = slug.trim_matches('-').chars().take(48).collect();
+        if slug.is_empty() {
+            "upload".to_string()
+        } else {
+            slug
+        }
+    }
+}
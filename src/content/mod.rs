@@ -0,0 +1,946 @@
+//! Runtime access to projects, blogs and photos.
+//!
+//! The server reads content through a [`ContentSource`], provided as [`Content`] through
+//! Leptos context and picked from [`crate::config::ContentBackend`].
+//!
+//! Sources hold every record regardless of its [`Status`]. The public server functions
+//! only return what is live, while the `admin_*` ones return everything to a signed-in
+//! admin. Unpublished items can also be opened with a signed preview token, see
+//! [`create_share_link`].
+//!
+//! Private projects are only ever sent to visitors as teasers, until they unlock the
+//! project with its passphrase or an invite link. Either sets a signed, per-project
+//! unlock cookie that [`get_project`] checks on the server.
+//!
+//! Pages load content with the `fetch_*` helpers inside resources. With a server (SSR and
+//! hydrate) they call the server functions below. The CSR build has no server, so they
+//! fetch the JSON files that `portfolio export` writes next to the bundle instead.
+
+#[cfg(feature = "ssr")]
+mod access;
+#[cfg(feature = "ssr")]
+mod directory;
+mod history;
+mod lint;
+mod media;
+#[cfg(feature = "csr")]
+mod remote;
+#[cfg(feature = "ssr")]
+mod sqlite;
+
+use std::fmt;
+use std::io;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use leptos::prelude::*;
+use leptos::server_fn::codec::{Json, MultipartData, MultipartFormData};
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "ssr", feature = "csr"))]
+use crate::data::Status;
+use crate::data::{all_blogs, all_projects, get_photos, Blog, Photo, Project};
+
+#[cfg(feature = "ssr")]
+pub use access::{AccessSigner, Grant};
+#[cfg(feature = "ssr")]
+pub use directory::DirectoryContent;
+pub use history::{AuditAction, AuditEntry, Record, Revision, ORIGINAL_AUTHOR};
+pub use lint::{lint_content, Assets, Problem};
+#[cfg(feature = "ssr")]
+pub use media::MediaStore;
+pub use media::{
+    apply_media_text, find_uses, MediaEntry, MediaItem, MediaKind, MediaUse, MAX_IMAGE_SIDE,
+    MEDIA_URL_DIR,
+};
+#[cfg(feature = "ssr")]
+pub use sqlite::SqliteContent;
+
+/// File names used wherever content is stored or published as JSON.
+pub const PROJECTS_FILE: &str = "projects.json";
+pub const BLOGS_FILE: &str = "blogs.json";
+pub const PHOTOS_FILE: &str = "photos.json";
+
+/// Where the CSR build expects the exported content files, relative to its base path.
+pub const CONTENT_URL_DIR: &str = "content";
+
+/// How many audit log entries the admin area shows.
+#[cfg(feature = "ssr")]
+const AUDIT_LOG_LIMIT: usize = 500;
+
+#[derive(Debug)]
+pub enum ContentError {
+    Io(io::Error),
+    Malformed(String),
+    Database(String),
+    Unavailable(String),
+    NotFound(String),
+    Invalid(String),
+    ReadOnly,
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentError::Io(err) => write!(f, "could not access content: {err}"),
+            ContentError::Malformed(err) => write!(f, "malformed content: {err}"),
+            ContentError::Database(err) => write!(f, "content database error: {err}"),
+            ContentError::Unavailable(err) => write!(f, "content unavailable: {err}"),
+            ContentError::NotFound(id) => write!(f, "no content with id `{id}`"),
+            ContentError::Invalid(msg) => f.write_str(msg),
+            ContentError::ReadOnly => f.write_str("This content source is read-only."),
+        }
+    }
+}
+
+impl std::error::Error for ContentError {}
+
+impl From<io::Error> for ContentError {
+    fn from(err: io::Error) -> Self {
+        ContentError::Io(err)
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<serde_json::Error> for ContentError {
+    fn from(err: serde_json::Error) -> Self {
+        ContentError::Malformed(err.to_string())
+    }
+}
+
+/// Somewhere projects, blogs and photos can be read from, and optionally edited.
+///
+/// Only the list methods are required; lookups by id default to searching the list and
+/// the editing methods default to [`ContentError::ReadOnly`]. Sources that can be edited
+/// should also keep [`Revision`]s and the audit log; a read-only source has neither.
+pub trait ContentSource: Send + Sync {
+    fn projects(&self) -> Result<Vec<Project>, ContentError>;
+
+    fn blogs(&self) -> Result<Vec<Blog>, ContentError>;
+
+    fn photos(&self) -> Result<Vec<Photo>, ContentError>;
+
+    fn project(&self, id: &str) -> Result<Option<Project>, ContentError> {
+        Ok(self.projects()?.into_iter().find(|p| &*p.id == id))
+    }
+
+    fn blog(&self, id: &str) -> Result<Option<Blog>, ContentError> {
+        Ok(self.blogs()?.into_iter().find(|b| &*b.id == id))
+    }
+
+    /// Creates or replaces a project. `original_id` is the id the project was loaded
+    /// with, or `None` for a new project, so that renaming an id replaces the old record.
+    fn save_project(
+        &self,
+        _original_id: Option<&str>,
+        _project: Project,
+    ) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    fn delete_project(&self, _id: &str) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    fn save_blog(&self, _original_id: Option<&str>, _blog: Blog) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    fn delete_blog(&self, _id: &str) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    fn save_photos(&self, _photos: Vec<Photo>) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    /// Stores a revision, assigning it the next number, and returns that number.
+    fn add_revision(&self, _revision: Revision) -> Result<u64, ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    /// The revisions stored under `kind` and `id`, newest first.
+    fn revisions(&self, _kind: ContentKind, _id: &str) -> Result<Vec<Revision>, ContentError> {
+        Ok(Vec::new())
+    }
+
+    fn revision(&self, _number: u64) -> Result<Option<Revision>, ContentError> {
+        Ok(None)
+    }
+
+    fn append_audit(&self, _entry: AuditEntry) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    /// The latest `limit` audit log entries, newest first.
+    fn audit_log(&self, _limit: usize) -> Result<Vec<AuditEntry>, ContentError> {
+        Ok(Vec::new())
+    }
+
+    /// The media library, newest upload first.
+    fn media(&self) -> Result<Vec<MediaItem>, ContentError> {
+        Ok(Vec::new())
+    }
+
+    /// Adds a library entry, or replaces the one with the same id.
+    fn save_media_item(&self, _item: MediaItem) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+
+    fn delete_media_item(&self, _id: &str) -> Result<(), ContentError> {
+        Err(ContentError::ReadOnly)
+    }
+}
+
+/// The kinds of content that have their own pages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentKind {
+    Project,
+    Blog,
+}
+
+impl ContentKind {
+    /// The route segment for this kind, e.g. `/project/:id`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentKind::Project => "project",
+            ContentKind::Blog => "blog",
+        }
+    }
+}
+
+/// A link an admin can hand out to open an item that visitors cannot normally see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShareLink {
+    /// Shows an unpublished item to reviewers; expires after a few days.
+    Preview,
+    /// Unlocks a private project for a recruiter; lasts longer.
+    Invite,
+}
+
+impl ShareLink {
+    pub fn label(self) -> &'static str {
+        match self {
+            ShareLink::Preview => "Create Preview Link",
+            ShareLink::Invite => "Create Invite Link",
+        }
+    }
+}
+
+/// A project page as the current visitor may see it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ProjectPage {
+    Full(Project),
+    /// A private project the visitor has not unlocked: only its [`Project::teaser`].
+    Locked(Project),
+}
+
+/// The content compiled into the binary from [`crate::data`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StaticContent;
+
+impl ContentSource for StaticContent {
+    fn projects(&self) -> Result<Vec<Project>, ContentError> {
+        Ok(all_projects())
+    }
+
+    fn blogs(&self) -> Result<Vec<Blog>, ContentError> {
+        Ok(all_blogs())
+    }
+
+    fn photos(&self) -> Result<Vec<Photo>, ContentError> {
+        Ok(get_photos())
+    }
+}
+
+/// The active content source, shared through Leptos context.
+#[derive(Clone)]
+pub struct Content(Arc<dyn ContentSource>);
+
+impl Content {
+    pub fn new(source: impl ContentSource + 'static) -> Self {
+        Self(Arc::new(source))
+    }
+
+    /// Opens the source selected in the server configuration.
+    #[cfg(feature = "ssr")]
+    pub fn from_config(backend: &crate::config::ContentBackend) -> Result<Self, ContentError> {
+        use crate::config::ContentBackend;
+
+        Ok(match backend {
+            ContentBackend::Static => Self::new(StaticContent),
+            ContentBackend::Directory(dir) => Self::new(DirectoryContent::open(dir)?),
+            ContentBackend::Sqlite(path) => Self::new(SqliteContent::open(path)?),
+        })
+    }
+}
+
+impl Deref for Content {
+    type Target = dyn ContentSource;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+#[server]
+pub async fn list_projects() -> Result<Vec<Project>, ServerFnError> {
+    let now = chrono::Utc::now();
+    let projects = expect_context::<Content>().projects()?;
+    Ok(projects
+        .into_iter()
+        .filter(|p| p.status.is_listed(now))
+        .map(public_view)
+        .collect())
+}
+
+/// Loads a project page. Unpublished projects need a valid `preview` token; private ones
+/// need an unlock cookie or a valid `invite` token, which then sets that cookie.
+#[server]
+pub async fn get_project(
+    id: String,
+    preview: Option<String>,
+    invite: Option<String>,
+) -> Result<Option<ProjectPage>, ServerFnError> {
+    let access = expect_context::<AccessSigner>();
+    let Some(project) = expect_context::<Content>().project(&id)? else {
+        return Ok(None);
+    };
+    let previewing = preview
+        .is_some_and(|token| access.verify(Grant::Preview, ContentKind::Project, &id, &token));
+    if !(previewing || project.status.is_public(chrono::Utc::now())) {
+        return Ok(None);
+    }
+    if previewing || !project.is_private() {
+        return Ok(Some(ProjectPage::Full(project.without_secrets())));
+    }
+
+    if let Some(invite) =
+        invite.filter(|token| access.verify(Grant::Unlock, ContentKind::Project, &id, token))
+    {
+        set_unlock_cookie(&id, &invite);
+        return Ok(Some(ProjectPage::Full(project.without_secrets())));
+    }
+    let cookie = crate::auth::ssr::request_cookie(&unlock_cookie_name(&id)).await?;
+    if cookie.is_some_and(|token| access.verify(Grant::Unlock, ContentKind::Project, &id, &token)) {
+        Ok(Some(ProjectPage::Full(project.without_secrets())))
+    } else {
+        Ok(Some(ProjectPage::Locked(project.teaser())))
+    }
+}
+
+/// Unlocks a private project for this browser when `passphrase` matches its own.
+#[server]
+pub async fn unlock_project(id: String, passphrase: String) -> Result<(), ServerFnError> {
+    crate::auth::ssr::require_same_origin().await?;
+    let project = expect_context::<Content>().project(&id)?;
+    let hash = project
+        .filter(|p| p.is_private() && p.status.is_public(chrono::Utc::now()))
+        .and_then(|p| p.passphrase_hash);
+    // Unknown projects and projects without a passphrase fail the same way as a wrong
+    // passphrase, so the error gives nothing away.
+    let unlocked = hash.is_some_and(|hash| crate::auth::ssr::verify_password(&passphrase, &hash));
+    if !unlocked {
+        return Err(ServerFnError::new("That passphrase is not right."));
+    }
+
+    let token = expect_context::<AccessSigner>().sign(Grant::Unlock, ContentKind::Project, &id);
+    set_unlock_cookie(&id, &token);
+    Ok(())
+}
+
+#[server]
+pub async fn list_blogs() -> Result<Vec<Blog>, ServerFnError> {
+    let now = chrono::Utc::now();
+    let blogs = expect_context::<Content>().blogs()?;
+    Ok(blogs
+        .into_iter()
+        .filter(|b| b.status.is_listed(now))
+        .collect())
+}
+
+/// Loads a blog post page. Unpublished posts need a valid `preview` token.
+#[server]
+pub async fn get_blog(id: String, preview: Option<String>) -> Result<Option<Blog>, ServerFnError> {
+    let blog = expect_context::<Content>().blog(&id)?;
+    Ok(blog.filter(|b| can_view(ContentKind::Blog, &id, b.status, preview.as_deref())))
+}
+
+#[server]
+pub async fn admin_projects() -> Result<Vec<Project>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    let projects = expect_context::<Content>().projects()?;
+    Ok(projects.into_iter().map(Project::without_secrets).collect())
+}
+
+#[server]
+pub async fn admin_project(id: String) -> Result<Option<Project>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    Ok(expect_context::<Content>()
+        .project(&id)?
+        .map(Project::without_secrets))
+}
+
+#[server]
+pub async fn admin_blogs() -> Result<Vec<Blog>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    Ok(expect_context::<Content>().blogs()?)
+}
+
+#[server]
+pub async fn admin_blog(id: String) -> Result<Option<Blog>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    Ok(expect_context::<Content>().blog(&id)?)
+}
+
+/// Creates a signed, expiring link to an item, see [`ShareLink`]. Returns the
+/// site-relative URL.
+#[server]
+pub async fn create_share_link(
+    csrf_token: String,
+    link: ShareLink,
+    kind: ContentKind,
+    id: String,
+) -> Result<String, ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let access = expect_context::<AccessSigner>();
+    let (grant, param) = match (link, kind) {
+        (ShareLink::Preview, _) => (Grant::Preview, "preview"),
+        (ShareLink::Invite, ContentKind::Project) => (Grant::Unlock, "invite"),
+        (ShareLink::Invite, ContentKind::Blog) => {
+            return Err(ServerFnError::new("Only projects can be private."));
+        }
+    };
+    let token = access.sign(grant, kind, &id);
+    let url = format!(
+        "{}?{param}={token}",
+        crate::routing::Route::content(kind, id.as_str()).path()
+    );
+    let action = AuditAction::CreatedLink {
+        link,
+        kind,
+        id: id.into(),
+    };
+    expect_context::<Content>().audit(&admin.username, action);
+    Ok(url)
+}
+
+#[server]
+pub async fn list_photos() -> Result<Vec<Photo>, ServerFnError> {
+    Ok(expect_context::<Content>().photos()?)
+}
+
+/// Saves a project. A non-empty `passphrase` replaces the project's passphrase; otherwise
+/// the stored one is kept, since admins never receive the hash.
+#[server(input = Json)]
+pub async fn save_project(
+    csrf_token: String,
+    original_id: Option<String>,
+    project: Project,
+    passphrase: Option<String>,
+) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let previous = original_id
+        .as_deref()
+        .map(|id| content.project(id))
+        .transpose()?
+        .flatten();
+    let mut project = project;
+    let media = content.media()?;
+    if let Some(photos) = &project.photos {
+        let mut photos = photos.to_vec();
+        apply_media_text(&mut photos, &media);
+        project.photos = Some(photos.into());
+    }
+    if let Some(blocks) = &project.blocks {
+        let mut blocks = blocks.to_vec();
+        for block in &mut blocks {
+            match block {
+                crate::data::Block::Image(photo) => {
+                    apply_media_text(std::slice::from_mut(photo), &media)
+                }
+                crate::data::Block::Gallery { photos, .. } => {
+                    let mut list = photos.to_vec();
+                    apply_media_text(&mut list, &media);
+                    *photos = list.into();
+                }
+                _ => {}
+            }
+        }
+        project.blocks = Some(blocks.into());
+    }
+    project.passphrase_hash = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => Some(crate::auth::ssr::hash_password(&passphrase)?.into()),
+        None => previous.as_ref().and_then(|p| p.passphrase_hash.clone()),
+    };
+    content.save_project(original_id.as_deref(), project.clone())?;
+
+    let id = project.id.clone();
+    let revision = content.record_change(
+        &admin.username,
+        previous.map(Record::Project),
+        Some(Record::Project(project)),
+        None,
+    )?;
+    content.audit(
+        &admin.username,
+        AuditAction::Saved {
+            kind: ContentKind::Project,
+            id,
+            revision,
+        },
+    );
+    Ok(())
+}
+
+#[server]
+pub async fn delete_project(csrf_token: String, id: String) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let previous = content.project(&id)?;
+    content.delete_project(&id)?;
+
+    let revision =
+        content.record_change(&admin.username, previous.map(Record::Project), None, None)?;
+    content.audit(
+        &admin.username,
+        AuditAction::Deleted {
+            kind: ContentKind::Project,
+            id: id.into(),
+            revision,
+        },
+    );
+    Ok(())
+}
+
+#[server(input = Json)]
+pub async fn save_blog(
+    csrf_token: String,
+    original_id: Option<String>,
+    blog: Blog,
+) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let previous = original_id
+        .as_deref()
+        .map(|id| content.blog(id))
+        .transpose()?
+        .flatten();
+    content.save_blog(original_id.as_deref(), blog.clone())?;
+
+    let id = blog.id.clone();
+    let revision = content.record_change(
+        &admin.username,
+        previous.map(Record::Blog),
+        Some(Record::Blog(blog)),
+        None,
+    )?;
+    content.audit(
+        &admin.username,
+        AuditAction::Saved {
+            kind: ContentKind::Blog,
+            id,
+            revision,
+        },
+    );
+    Ok(())
+}
+
+#[server]
+pub async fn delete_blog(csrf_token: String, id: String) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let previous = content.blog(&id)?;
+    content.delete_blog(&id)?;
+
+    let revision =
+        content.record_change(&admin.username, previous.map(Record::Blog), None, None)?;
+    content.audit(
+        &admin.username,
+        AuditAction::Deleted {
+            kind: ContentKind::Blog,
+            id: id.into(),
+            revision,
+        },
+    );
+    Ok(())
+}
+
+#[server(input = Json)]
+pub async fn save_photos(csrf_token: String, photos: Vec<Photo>) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let mut photos = photos;
+    apply_media_text(&mut photos, &content.media()?);
+    let count = photos.len();
+    content.save_photos(photos)?;
+    content.audit(&admin.username, AuditAction::SavedPhotos { count });
+    Ok(())
+}
+
+/// The history of the item now called `id`, newest first.
+#[server]
+pub async fn admin_history(kind: ContentKind, id: String) -> Result<Vec<Revision>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    let history = expect_context::<Content>().history(kind, &id)?;
+    Ok(history
+        .into_iter()
+        .map(|revision| Revision {
+            record: revision.record.map(Record::without_secrets),
+            ..revision
+        })
+        .collect())
+}
+
+/// Restores the item now called `id` to the state stored in revision `number`, which
+/// must be part of its history. Restoring a deleted item brings it back.
+#[server]
+pub async fn rollback(
+    csrf_token: String,
+    kind: ContentKind,
+    id: String,
+    number: u64,
+) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let target = content
+        .history(kind, &id)?
+        .into_iter()
+        .find(|r| r.number == number)
+        .ok_or_else(|| {
+            ServerFnError::new(format!(
+                "Revision #{number} is not part of this item's history."
+            ))
+        })?;
+    let Some(record) = target.record else {
+        return Err(ServerFnError::new(
+            "That revision is a deletion; delete the item instead.",
+        ));
+    };
+
+    let previous = match kind {
+        ContentKind::Project => content.project(&id)?.map(Record::Project),
+        ContentKind::Blog => content.blog(&id)?.map(Record::Blog),
+    };
+    let original_id = previous.as_ref().map(|_| id.as_str());
+    match record.clone() {
+        Record::Project(project) => content.save_project(original_id, project)?,
+        Record::Blog(blog) => content.save_blog(original_id, blog)?,
+    }
+
+    let restored_id = record.id().clone();
+    let revision = content.record_change(&admin.username, previous, Some(record), Some(number))?;
+    let action = AuditAction::RolledBack {
+        kind,
+        id: restored_id,
+        to: number,
+        revision,
+    };
+    content.audit(&admin.username, action);
+    Ok(())
+}
+
+/// The media library, with where each file is used.
+#[server]
+pub async fn admin_media() -> Result<Vec<MediaEntry>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    let content = expect_context::<Content>();
+    let (projects, blogs, gallery) = (content.projects()?, content.blogs()?, content.photos()?);
+    Ok(content
+        .media()?
+        .into_iter()
+        .map(|item| MediaEntry {
+            uses: find_uses(&item, &projects, &blogs, &gallery),
+            item,
+        })
+        .collect())
+}
+
+/// Adds files to the media library. The form must send its `csrf_token` field before
+/// any `files`.
+#[server(input = MultipartFormData)]
+pub async fn upload_media(data: MultipartData) -> Result<Vec<MediaItem>, ServerFnError> {
+    let media = expect_context::<MediaStore>();
+    let content = expect_context::<Content>();
+    let mut form = data
+        .into_inner()
+        .ok_or_else(|| ServerFnError::new("Expected a multipart form."))?;
+
+    let mut admin = None;
+    let mut uploaded = Vec::new();
+    while let Some(mut field) = form.next_field().await? {
+        match field.name() {
+            Some("csrf_token") => {
+                let token = field.text().await?;
+                admin = Some(crate::auth::ssr::require_admin(&token).await?);
+            }
+            Some("files") => {
+                let Some(admin) = &admin else {
+                    return Err(ServerFnError::new("Invalid CSRF token."));
+                };
+                let name = field.file_name().unwrap_or_default().to_string();
+                if name.is_empty() {
+                    continue;
+                }
+                let mut bytes = Vec::new();
+                while let Some(chunk) = field.chunk().await? {
+                    bytes.extend_from_slice(&chunk);
+                    if bytes.len() as u64 > media.max_upload_bytes() {
+                        let limit = media.max_upload_bytes() / (1024 * 1024);
+                        return Err(ServerFnError::new(format!(
+                            "`{name}` is larger than {limit} MB."
+                        )));
+                    }
+                }
+
+                // Decoding and resizing is CPU-bound, so keep it off the async workers.
+                let item = {
+                    let (media, name) = (media.clone(), name.clone());
+                    tokio::task::spawn_blocking(move || media.store(&name, &bytes)).await??
+                };
+                if let Err(err) = content.save_media_item(item.clone()) {
+                    media.remove(&item)?;
+                    return Err(err.into());
+                }
+                content.audit(
+                    &admin.username,
+                    AuditAction::UploadedMedia {
+                        id: item.id.clone(),
+                    },
+                );
+                uploaded.push(item);
+            }
+            _ => {}
+        }
+    }
+    Ok(uploaded)
+}
+
+/// Updates the caption and alt text of a library file. Photos that show it pick these
+/// up when they are next saved without their own.
+#[server]
+pub async fn update_media(
+    csrf_token: String,
+    id: String,
+    caption: String,
+    alt: String,
+) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let mut item = content
+        .media()?
+        .into_iter()
+        .find(|item| *item.id == id)
+        .ok_or_else(|| ContentError::NotFound(id.clone()))?;
+    item.caption = caption.trim().into();
+    item.alt = alt.trim().into();
+    content.save_media_item(item)?;
+    content.audit(&admin.username, AuditAction::EditedMedia { id: id.into() });
+    Ok(())
+}
+
+/// Deletes a library file, refusing while any content still references it.
+#[server]
+pub async fn delete_media(csrf_token: String, id: String) -> Result<(), ServerFnError> {
+    let admin = crate::auth::ssr::require_admin(&csrf_token).await?;
+    let content = expect_context::<Content>();
+    let item = content
+        .media()?
+        .into_iter()
+        .find(|item| *item.id == id)
+        .ok_or_else(|| ContentError::NotFound(id.clone()))?;
+    let uses = find_uses(
+        &item,
+        &content.projects()?,
+        &content.blogs()?,
+        &content.photos()?,
+    );
+    if !uses.is_empty() {
+        return Err(ServerFnError::new(format!(
+            "`{id}` is still used in {} place(s); remove those references first.",
+            uses.len()
+        )));
+    }
+
+    content.delete_media_item(&id)?;
+    expect_context::<MediaStore>().remove(&item)?;
+    content.audit(&admin.username, AuditAction::DeletedMedia { id: id.into() });
+    Ok(())
+}
+
+/// The latest entries of the audit log, newest first.
+#[server]
+pub async fn admin_audit_log() -> Result<Vec<AuditEntry>, ServerFnError> {
+    crate::auth::ssr::require_session().await?;
+    Ok(expect_context::<Content>().audit_log(AUDIT_LOG_LIMIT)?)
+}
+
+pub async fn fetch_projects() -> Result<Vec<Project>, ServerFnError> {
+    #[cfg(feature = "csr")]
+    return Ok(listed(remote::load(PROJECTS_FILE).await?, |p: &Project| {
+        p.status
+    }));
+    #[cfg(not(feature = "csr"))]
+    list_projects().await
+}
+
+/// Loads a project page, passing along the `preview` and `invite` tokens from its URL.
+/// The CSR build has no way to check tokens: it only serves published projects, and only
+/// the teasers of private ones.
+pub async fn fetch_project(
+    id: String,
+    #[cfg_attr(feature = "csr", allow(unused_variables))] preview: Option<String>,
+    #[cfg_attr(feature = "csr", allow(unused_variables))] invite: Option<String>,
+) -> Result<Option<ProjectPage>, ServerFnError> {
+    #[cfg(feature = "csr")]
+    return Ok(remote::load::<Project>(PROJECTS_FILE)
+        .await?
+        .into_iter()
+        .find(|p| *p.id == id && p.status.is_public(chrono::Utc::now()))
+        .map(|p| {
+            if p.is_private() {
+                ProjectPage::Locked(p.teaser())
+            } else {
+                ProjectPage::Full(p)
+            }
+        }));
+    #[cfg(not(feature = "csr"))]
+    get_project(id, preview, invite).await
+}
+
+pub async fn fetch_blogs() -> Result<Vec<Blog>, ServerFnError> {
+    #[cfg(feature = "csr")]
+    return Ok(listed(remote::load(BLOGS_FILE).await?, |b: &Blog| b.status));
+    #[cfg(not(feature = "csr"))]
+    list_blogs().await
+}
+
+/// Loads a blog post page; see [`fetch_project`].
+pub async fn fetch_blog(
+    id: String,
+    #[cfg_attr(feature = "csr", allow(unused_variables))] preview: Option<String>,
+) -> Result<Option<Blog>, ServerFnError> {
+    #[cfg(feature = "csr")]
+    return Ok(remote::load::<Blog>(BLOGS_FILE)
+        .await?
+        .into_iter()
+        .find(|b| *b.id == id && b.status.is_public(chrono::Utc::now())));
+    #[cfg(not(feature = "csr"))]
+    get_blog(id, preview).await
+}
+
+pub async fn fetch_photos() -> Result<Vec<Photo>, ServerFnError> {
+    #[cfg(feature = "csr")]
+    return Ok(remote::load(PHOTOS_FILE).await?);
+    #[cfg(not(feature = "csr"))]
+    list_photos().await
+}
+
+/// Keeps the items that should appear in listings right now.
+#[cfg(feature = "csr")]
+fn listed<T>(items: Vec<T>, status: impl Fn(&T) -> Status) -> Vec<T> {
+    let now = chrono::Utc::now();
+    items
+        .into_iter()
+        .filter(|item| status(item).is_listed(now))
+        .collect()
+}
+
+#[cfg(feature = "ssr")]
+fn can_view(kind: ContentKind, id: &str, status: Status, preview: Option<&str>) -> bool {
+    status.is_public(chrono::Utc::now())
+        || preview.is_some_and(|token| {
+            expect_context::<AccessSigner>().verify(Grant::Preview, kind, id, token)
+        })
+}
+
+/// A project as it may appear in public listings: private ones as teasers only.
+#[cfg(feature = "ssr")]
+fn public_view(project: Project) -> Project {
+    if project.is_private() {
+        project.teaser()
+    } else {
+        project.without_secrets()
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn unlock_cookie_name(id: &str) -> String {
+    format!("portfolio_unlock_{id}")
+}
+
+#[cfg(feature = "ssr")]
+fn set_unlock_cookie(id: &str, token: &str) {
+    use crate::auth::ssr::{AuthState, SameSite};
+
+    let ttl = expect_context::<AccessSigner>().ttl(Grant::Unlock);
+    expect_context::<AuthState>().set_cookie(&unlock_cookie_name(id), token, ttl, SameSite::Lax);
+}
+
+/// Checks the fields every stored record needs before a backend accepts it.
+#[cfg(feature = "ssr")]
+fn validate_record(kind: &str, id: &str, title: &str) -> Result<(), ContentError> {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ContentError::Invalid(format!(
+            "The {kind} id must be non-empty and use only letters, digits, `-` and `_`."
+        )));
+    }
+    if title.trim().is_empty() {
+        return Err(ContentError::Invalid(format!("The {kind} needs a title.")));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+fn validate_photos(photos: &[Photo]) -> Result<(), ContentError> {
+    if photos.iter().any(|p| p.url.trim().is_empty()) {
+        return Err(ContentError::Invalid("Every photo needs a URL.".into()));
+    }
+    Ok(())
+}
+
+/// Inserts `item`, or replaces the record loaded as `original_id`, refusing duplicate ids.
+#[cfg(feature = "ssr")]
+fn upsert<T>(
+    items: &mut Vec<T>,
+    original_id: Option<&str>,
+    item: T,
+    id_of: impl Fn(&T) -> &str,
+) -> Result<(), ContentError> {
+    let new_id = id_of(&item).to_string();
+    let clashes = items
+        .iter()
+        .any(|existing| id_of(existing) == new_id && Some(new_id.as_str()) != original_id);
+    if clashes {
+        return Err(ContentError::Invalid(format!(
+            "The id `{new_id}` is already in use."
+        )));
+    }
+
+    match original_id {
+        Some(id) => {
+            let index = items
+                .iter()
+                .position(|existing| id_of(existing) == id)
+                .ok_or_else(|| ContentError::NotFound(id.to_string()))?;
+            items[index] = item;
+        }
+        None => items.push(item),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+fn remove<T>(items: &mut Vec<T>, id: &str, id_of: impl Fn(&T) -> &str) -> Result<(), ContentError> {
+    let index = items
+        .iter()
+        .position(|item| id_of(item) == id)
+        .ok_or_else(|| ContentError::NotFound(id.to_string()))?;
+    items.remove(index);
+    Ok(())
+}
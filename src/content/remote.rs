@@ -0,0 +1,57 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+
+use gloo_net::http::Request;
+use send_wrapper::SendWrapper;
+use serde::de::DeserializeOwned;
+
+use super::{ContentError, CONTENT_URL_DIR};
+
+thread_local! {
+    static CACHE: RefCell<HashMap<&'static str, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Fetches one of the content files deployed next to the CSR bundle.
+///
+/// The URL is relative, so it resolves against the page's `<base>` and works under any
+/// base path. Successful responses are cached for the life of the page; failures are
+/// not, so retrying fetches again.
+pub fn load<T>(file: &'static str) -> impl Future<Output = Result<Vec<T>, ContentError>> + Send
+where
+    T: Clone + DeserializeOwned + 'static,
+{
+    // Resources need `Send` futures; browser fetches never leave the main thread.
+    SendWrapper::new(fetch(file))
+}
+
+async fn fetch<T>(file: &'static str) -> Result<Vec<T>, ContentError>
+where
+    T: Clone + DeserializeOwned + 'static,
+{
+    let cached = CACHE.with_borrow(|cache| cache.get(file).cloned());
+    if let Some(items) = cached.and_then(|items| items.downcast::<Vec<T>>().ok()) {
+        return Ok(items.as_ref().clone());
+    }
+
+    let url = format!("{CONTENT_URL_DIR}/{file}");
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|err| ContentError::Unavailable(err.to_string()))?;
+    if !response.ok() {
+        return Err(ContentError::Unavailable(format!(
+            "{url} returned {}",
+            response.status()
+        )));
+    }
+    let items: Vec<T> = response
+        .json()
+        .await
+        .map_err(|err| ContentError::Malformed(err.to_string()))?;
+
+    CACHE.with_borrow_mut(|cache| cache.insert(file, Rc::new(items.clone())));
+    Ok(items)
+}
@@ -0,0 +1,300 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{
+    remove, upsert, validate_photos, validate_record, AuditEntry, ContentError, ContentKind,
+    ContentSource, MediaItem, Revision,
+};
+use crate::data::{all_blogs, all_projects, get_photos, Blog, Photo, Project};
+
+const PROJECTS: &str = "projects";
+const BLOGS: &str = "blogs";
+const PHOTOS: &str = "photos";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS projects (id TEXT PRIMARY KEY, position INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS blogs (id TEXT PRIMARY KEY, position INTEGER NOT NULL, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS photos (position INTEGER PRIMARY KEY, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS revisions (
+        number INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind TEXT NOT NULL,
+        item_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS revisions_by_item ON revisions (kind, item_id);
+    CREATE TABLE IF NOT EXISTS audit_log (id INTEGER PRIMARY KEY AUTOINCREMENT, data TEXT NOT NULL);
+    CREATE TABLE IF NOT EXISTS media (id TEXT PRIMARY KEY, data TEXT NOT NULL);
+";
+
+impl From<rusqlite::Error> for ContentError {
+    fn from(err: rusqlite::Error) -> Self {
+        ContentError::Database(err.to_string())
+    }
+}
+
+/// Content stored in a SQLite database, one JSON document per row.
+///
+/// Each table keeps a `position` column so listings come back in the order they were
+/// edited in. A newly created database is seeded from the built-in data.
+pub struct SqliteContent {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteContent {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ContentError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let is_new = !path.exists();
+        let mut conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        if is_new {
+            let tx = conn.transaction()?;
+            write_records(&tx, PROJECTS, &all_projects(), |p| &*p.id)?;
+            write_records(&tx, BLOGS, &all_blogs(), |b| &*b.id)?;
+            write_photos(&tx, &get_photos())?;
+            tx.commit()?;
+        }
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+impl ContentSource for SqliteContent {
+    fn projects(&self) -> Result<Vec<Project>, ContentError> {
+        read_all(&self.conn.lock().unwrap(), PROJECTS)
+    }
+
+    fn blogs(&self) -> Result<Vec<Blog>, ContentError> {
+        read_all(&self.conn.lock().unwrap(), BLOGS)
+    }
+
+    fn photos(&self) -> Result<Vec<Photo>, ContentError> {
+        read_all(&self.conn.lock().unwrap(), PHOTOS)
+    }
+
+    fn project(&self, id: &str) -> Result<Option<Project>, ContentError> {
+        read_one(&self.conn.lock().unwrap(), PROJECTS, id)
+    }
+
+    fn blog(&self, id: &str) -> Result<Option<Blog>, ContentError> {
+        read_one(&self.conn.lock().unwrap(), BLOGS, id)
+    }
+
+    fn save_project(
+        &self,
+        original_id: Option<&str>,
+        project: Project,
+    ) -> Result<(), ContentError> {
+        validate_record("project", &project.id, &project.title)?;
+        edit_records(
+            &mut self.conn.lock().unwrap(),
+            PROJECTS,
+            |p: &Project| &*p.id,
+            |projects| upsert(projects, original_id, project, |p| &*p.id),
+        )
+    }
+
+    fn delete_project(&self, id: &str) -> Result<(), ContentError> {
+        edit_records(
+            &mut self.conn.lock().unwrap(),
+            PROJECTS,
+            |p: &Project| &*p.id,
+            |projects| remove(projects, id, |p| &*p.id),
+        )
+    }
+
+    fn save_blog(&self, original_id: Option<&str>, blog: Blog) -> Result<(), ContentError> {
+        validate_record("blog post", &blog.id, &blog.title)?;
+        edit_records(
+            &mut self.conn.lock().unwrap(),
+            BLOGS,
+            |b: &Blog| &*b.id,
+            |blogs| upsert(blogs, original_id, blog, |b| &*b.id),
+        )
+    }
+
+    fn delete_blog(&self, id: &str) -> Result<(), ContentError> {
+        edit_records(
+            &mut self.conn.lock().unwrap(),
+            BLOGS,
+            |b: &Blog| &*b.id,
+            |blogs| remove(blogs, id, |b| &*b.id),
+        )
+    }
+
+    fn save_photos(&self, photos: Vec<Photo>) -> Result<(), ContentError> {
+        validate_photos(&photos)?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        write_photos(&tx, &photos)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn add_revision(&self, revision: Revision) -> Result<u64, ContentError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO revisions (kind, item_id, data) VALUES (?1, ?2, ?3)",
+            params![
+                revision.kind.as_str(),
+                &*revision.id,
+                serde_json::to_string(&revision)?
+            ],
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }
+
+    fn revisions(&self, kind: ContentKind, id: &str) -> Result<Vec<Revision>, ContentError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT number, data FROM revisions WHERE kind = ?1 AND item_id = ?2 ORDER BY number DESC")?;
+        let rows = stmt.query_map(params![kind.as_str(), id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.map(|row| {
+            let (number, data) = row?;
+            read_revision(number, &data)
+        })
+        .collect()
+    }
+
+    fn revision(&self, number: u64) -> Result<Option<Revision>, ContentError> {
+        let data = self
+            .conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT data FROM revisions WHERE number = ?1",
+                [number as i64],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        data.map(|data| read_revision(number as i64, &data))
+            .transpose()
+    }
+
+    fn append_audit(&self, entry: AuditEntry) -> Result<(), ContentError> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO audit_log (data) VALUES (?1)",
+            [serde_json::to_string(&entry)?],
+        )?;
+        Ok(())
+    }
+
+    fn audit_log(&self, limit: usize) -> Result<Vec<AuditEntry>, ContentError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT data FROM audit_log ORDER BY id DESC LIMIT ?1")?;
+        let rows = stmt.query_map([limit as i64], |row| row.get::<_, String>(0))?;
+        rows.map(|data| Ok(serde_json::from_str(&data?)?)).collect()
+    }
+
+    fn media(&self) -> Result<Vec<MediaItem>, ContentError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT data FROM media ORDER BY rowid DESC")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.map(|data| Ok(serde_json::from_str(&data?)?)).collect()
+    }
+
+    fn save_media_item(&self, item: MediaItem) -> Result<(), ContentError> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO media (id, data) VALUES (?1, ?2) ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+            params![&*item.id, serde_json::to_string(&item)?],
+        )?;
+        Ok(())
+    }
+
+    fn delete_media_item(&self, id: &str) -> Result<(), ContentError> {
+        let deleted = self
+            .conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM media WHERE id = ?1", [id])?;
+        if deleted == 0 {
+            return Err(ContentError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// The number is only known once the row is inserted, so the column is authoritative.
+fn read_revision(number: i64, data: &str) -> Result<Revision, ContentError> {
+    let mut revision: Revision = serde_json::from_str(data)?;
+    revision.number = number as u64;
+    Ok(revision)
+}
+
+fn read_all<T: DeserializeOwned>(conn: &Connection, table: &str) -> Result<Vec<T>, ContentError> {
+    let mut stmt = conn.prepare(&format!("SELECT data FROM {table} ORDER BY position"))?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    rows.map(|data| Ok(serde_json::from_str(&data?)?)).collect()
+}
+
+fn read_one<T: DeserializeOwned>(
+    conn: &Connection,
+    table: &str,
+    id: &str,
+) -> Result<Option<T>, ContentError> {
+    let data = conn
+        .query_row(
+            &format!("SELECT data FROM {table} WHERE id = ?1"),
+            [id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    data.map(|data| Ok(serde_json::from_str(&data)?))
+        .transpose()
+}
+
+/// Loads a table, applies `edit` to the list, and writes it back in one transaction, so
+/// the id and ordering rules match the other backends exactly.
+fn edit_records<T: Serialize + DeserializeOwned>(
+    conn: &mut Connection,
+    table: &str,
+    id_of: impl Fn(&T) -> &str,
+    edit: impl FnOnce(&mut Vec<T>) -> Result<(), ContentError>,
+) -> Result<(), ContentError> {
+    let tx = conn.transaction()?;
+    let mut records = read_all(&tx, table)?;
+    edit(&mut records)?;
+    write_records(&tx, table, &records, id_of)?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn write_records<T: Serialize>(
+    conn: &Connection,
+    table: &str,
+    records: &[T],
+    id_of: impl Fn(&T) -> &str,
+) -> Result<(), ContentError> {
+    conn.execute(&format!("DELETE FROM {table}"), [])?;
+    let mut stmt = conn.prepare(&format!(
+        "INSERT INTO {table} (id, position, data) VALUES (?1, ?2, ?3)"
+    ))?;
+    for (position, record) in records.iter().enumerate() {
+        stmt.execute(params![
+            id_of(record),
+            position as i64,
+            serde_json::to_string(record)?
+        ])?;
+    }
+    Ok(())
+}
+
+fn write_photos(conn: &Connection, photos: &[Photo]) -> Result<(), ContentError> {
+    conn.execute("DELETE FROM photos", [])?;
+    let mut stmt = conn.prepare("INSERT INTO photos (position, data) VALUES (?1, ?2)")?;
+    for (position, photo) in photos.iter().enumerate() {
+        stmt.execute(params![position as i64, serde_json::to_string(photo)?])?;
+    }
+    Ok(())
+}
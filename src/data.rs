@@ -1,144 +1,438 @@
-#[derive(Clone, Debug, PartialEq)]
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Shared, immutable text. Content records are cloned freely into views, so their
+/// fields are reference-counted rather than owned `String`s.
+pub type Text = Arc<str>;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Project {
-    pub id: &'static str,
-    pub title: &'static str,
-    pub subtitle: &'static str,
-    pub tag: &'static str,
-    pub description: &'static str,
-    pub overview: &'static str,
-    pub role: &'static str,
-    pub technologies: Option<Vec<&'static str>>,
-    pub live_link: Option<&'static str>,
-    pub code_link: Option<&'static str>,
-    pub paper_link: Option<&'static str>,
-    pub posters: Option<Vec<Poster>>,
-    pub photos: Option<Vec<Photo>>,
+    pub id: Text,
+    pub title: Text,
+    pub subtitle: Text,
+    pub tag: Text,
+    pub description: Text,
+    pub overview: Text,
+    pub role: Text,
+    pub technologies: Option<Arc<[Text]>>,
+    pub live_link: Option<Text>,
+    pub code_link: Option<Text>,
+    pub paper_link: Option<Text>,
+    /// Where `paper_link` was mirrored from, when it points at a local copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_source: Option<Text>,
+    pub posters: Option<Arc<[Poster]>>,
+    pub photos: Option<Arc<[Photo]>>,
+    /// The case study as a sequence of sections, shown after the fields above. Simple
+    /// projects leave it empty and only use the fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<Arc<[Block]>>,
+    /// Key of an interactive demo in [`crate::demos::DEMOS`], shown after the blocks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demo: Option<Text>,
+    pub status: Status,
+    pub visibility: Visibility,
+    /// Argon2 hash of the passphrase that unlocks a private project. Server-side only:
+    /// it is stripped by [`Project::without_secrets`] before a project leaves the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase_hash: Option<Text>,
+}
+
+impl Project {
+    pub fn is_private(&self) -> bool {
+        self.visibility == Visibility::Private
+    }
+
+    /// The card-level fields of a project (title, subtitle, tag and description), which
+    /// are all that visitors see of a private project until they unlock it.
+    pub fn teaser(&self) -> Project {
+        Project {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            subtitle: self.subtitle.clone(),
+            tag: self.tag.clone(),
+            description: self.description.clone(),
+            status: self.status,
+            visibility: self.visibility,
+            ..Project::default()
+        }
+    }
+
+    pub fn without_secrets(self) -> Project {
+        Project {
+            passphrase_hash: None,
+            ..self
+        }
+    }
+}
+
+/// Who can open a project's page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Public,
+    /// Listed as a teaser; the full case study needs the passphrase or an invite link.
+    Private,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Blog {
-    pub id: &'static str,
-    pub title: &'static str,
-    pub subtitle: &'static str,
-    pub tag: &'static str,
-    pub description: &'static str,
-    pub overview: &'static str,
-    pub live_link: Option<&'static str>,
+    pub id: Text,
+    pub title: Text,
+    pub subtitle: Text,
+    pub tag: Text,
+    pub description: Text,
+    pub overview: Text,
+    pub live_link: Option<Text>,
+    pub status: Status,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Where a project or blog post is in its publishing lifecycle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum Status {
+    /// Only visible to the admin and through preview links.
+    Draft,
+    /// Goes live on its own once `publish_at` has passed.
+    Scheduled { publish_at: DateTime<Utc> },
+    #[default]
+    Published,
+    /// No longer listed, but old links keep working.
+    Archived,
+}
+
+impl Status {
+    /// Whether the item appears in listings and anything else that enumerates content.
+    pub fn is_listed(&self, now: DateTime<Utc>) -> bool {
+        match self {
+            Status::Published => true,
+            Status::Scheduled { publish_at } => *publish_at <= now,
+            Status::Draft | Status::Archived => false,
+        }
+    }
+
+    /// Whether the item's own page can be opened without a preview link.
+    pub fn is_public(&self, now: DateTime<Utc>) -> bool {
+        self.is_listed(now) || *self == Status::Archived
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Draft => "Draft",
+            Status::Scheduled { .. } => "Scheduled",
+            Status::Published => "Published",
+            Status::Archived => "Archived",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Poster {
-    pub name: &'static str,
-    pub url: &'static str,
+    pub name: Text,
+    /// The PDF.
+    pub url: Text,
+    /// An image of the poster, shown as its thumbnail.
+    pub preview: Option<Text>,
+    pub pages: Option<u32>,
+    /// Where `url` was mirrored from, when it points at a local copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Text>,
+}
+
+/// One section of a project's case study, rendered by [`crate::blocks::Blocks`].
+///
+/// Stored as JSON objects tagged by `type`, e.g. `{"type": "quote", "text": "…"}`.
+/// `body` text supports the light markup described in [`crate::blocks`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    RichText {
+        heading: Option<Text>,
+        body: Text,
+    },
+    Image(Photo),
+    Gallery {
+        heading: Option<Text>,
+        photos: Arc<[Photo]>,
+    },
+    Code {
+        /// Shown as a label; there is no syntax highlighting.
+        language: Option<Text>,
+        caption: Option<Text>,
+        code: Text,
+    },
+    Quote {
+        text: Text,
+        attribution: Option<Text>,
+    },
+    Metrics {
+        heading: Option<Text>,
+        items: Arc<[Metric]>,
+    },
+    /// A YouTube or Vimeo page is embedded with its player; any other URL is played
+    /// as a video file.
+    Video {
+        url: Text,
+        #[serde(default)]
+        title: Text,
+    },
+    Callout {
+        #[serde(default)]
+        tone: Tone,
+        title: Option<Text>,
+        body: Text,
+    },
+    Chart(Chart),
+}
+
+impl Block {
+    /// The images the block shows.
+    pub fn photos(&self) -> &[Photo] {
+        match self {
+            Block::Image(photo) => std::slice::from_ref(photo),
+            Block::Gallery { photos, .. } => photos,
+            _ => &[],
+        }
+    }
+}
+
+/// A headline number, such as "3×" over "faster builds".
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Metric {
+    pub value: Text,
+    pub label: Text,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Numeric results drawn as an SVG chart by [`crate::chart::SvgChart`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub title: Text,
+    pub x_label: Option<Text>,
+    pub y_label: Option<Text>,
+    /// Appended to values, such as `%` or `×`.
+    pub unit: Option<Text>,
+    /// The x axis of bar charts, and of line charts whose series have `values`.
+    pub categories: Arc<[Text]>,
+    pub series: Arc<[Series]>,
+}
+
+impl Chart {
+    /// Whether the series are plotted by category (their `values`) rather than by number
+    /// (their `points`).
+    pub fn is_categorical(&self) -> bool {
+        match self.kind {
+            ChartKind::Bar => true,
+            ChartKind::Line => !self.categories.is_empty(),
+            ChartKind::Scatter => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    #[default]
+    Bar,
+    Line,
+    Scatter,
+}
+
+/// One named set of numbers in a chart.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Series {
+    pub name: Text,
+    /// One value per category.
+    pub values: Arc<[f64]>,
+    /// `[x, y]` pairs, for scatter charts and line charts over numbers.
+    pub points: Arc<[[f64; 2]]>,
+}
+
+/// How a callout is coloured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tone {
+    #[default]
+    Info,
+    Success,
+    Warning,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Photo {
-    pub url: &'static str,
-    pub caption: &'static str,
+    pub url: Text,
+    pub caption: Text,
+    /// Describes the image for screen readers; the caption is used when empty.
+    pub alt: Text,
+}
+
+impl Photo {
+    pub fn alt_text(&self) -> Text {
+        if self.alt.is_empty() {
+            self.caption.clone()
+        } else {
+            self.alt.clone()
+        }
+    }
 }
 
 pub fn get_photos() -> Vec<Photo> {
     vec![
         Photo {
-            url: "images/temple-photo.jpg",
-            caption: "Angkor Wat, Cambodia",
+            url: "images/temple-photo.jpg".into(),
+            caption: "Angkor Wat, Cambodia".into(),
+            alt: "The towers of Angkor Wat reflected in the pond in front of the temple".into(),
         },
         Photo {
-            url: "images/SpotWelderFront.jpg",
-            caption: "DIY Spotwelder - Front View",
+            url: "images/SpotWelderFront.jpg".into(),
+            caption: "DIY Spotwelder - Front View".into(),
+            alt: "Front panel of the homemade spot welder with its switch and welding leads".into(),
         },
         Photo {
-            url: "images/SpotWelderTop.jpg",
-            caption: "DIY Spotwelder - Top View",
+            url: "images/SpotWelderTop.jpg".into(),
+            caption: "DIY Spotwelder - Top View".into(),
+            alt: "The spot welder seen from above, showing the transformer inside its case".into(),
         },
     ]
 }
 
+/// The published projects, as shown on the site.
 pub fn get_projects() -> Vec<Project> {
+    let now = Utc::now();
+    all_projects()
+        .into_iter()
+        .filter(|p| p.status.is_listed(now))
+        .collect()
+}
+
+/// Every built-in project, including drafts and scheduled ones.
+pub fn all_projects() -> Vec<Project> {
     vec![
         Project {
-            id: "genezippers",
-            title: "GeneZippers: DNA Compression",
-            subtitle: "From Bases to Bits: An Analysis of Early DNA Compression Algorithms",
-            tag: "Research",
-            description: "Analysis of early DNA compression algorithms comparing Huffman Coding, DNAzip, and Biocompress 1 for genomic data.",
-            overview: "This project evaluates three distinct data compression strategies to assess their efficacy in handling massive genomic datasets by comparing a general text-based approach (Huffman Coding) against two specialized DNA compressors: DNAzip (reference-based) and Biocompress 1 (non-reference-based).",
-            role: "I focused on implementing and analyzing DNAzip, a reference-based DNA compression algorithm. This involved understanding the algorithm's approach to leveraging sequence similarity and evaluating its performance across different genomic datasets.",
-            technologies: Some(vec!["Python", "Bioinformatics", "Data Compression", "Algorithm Analysis"]),
-            live_link: Some("https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/"),
-            code_link: Some("https://github.com/Rawleo/genezippers_comps"),
-            paper_link: Some("https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/paper.html"),
+            id: "genezippers".into(),
+            status: Status::Published,
+            visibility: Visibility::Public,
+            passphrase_hash: None,
+            title: "GeneZippers: DNA Compression".into(),
+            subtitle: "From Bases to Bits: An Analysis of Early DNA Compression Algorithms".into(),
+            tag: "Research".into(),
+            description: "Analysis of early DNA compression algorithms comparing Huffman Coding, DNAzip, and Biocompress 1 for genomic data.".into(),
+            overview: "This project evaluates three distinct data compression strategies to assess their efficacy in handling massive genomic datasets by comparing a general text-based approach (Huffman Coding) against two specialized DNA compressors: DNAzip (reference-based) and Biocompress 1 (non-reference-based).".into(),
+            role: "I focused on implementing and analyzing DNAzip, a reference-based DNA compression algorithm. This involved understanding the algorithm's approach to leveraging sequence similarity and evaluating its performance across different genomic datasets.".into(),
+            technologies: Some(vec!["Python".into(), "Bioinformatics".into(), "Data Compression".into(), "Algorithm Analysis".into()].into()),
+            live_link: Some("https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/".into()),
+            code_link: Some("https://github.com/Rawleo/genezippers_comps".into()),
+            paper_link: Some("https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/paper.html".into()),
+            paper_source: None,
             posters: Some(vec![
-                Poster { name: "Gavin: Biocompress 1", url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/Saxer_Poster.pdf" },
-                Poster { name: "Jared: DNAzip", url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/ArroyoRuiz_Poster.pdf" },
-                Poster { name: "Ryan: DNAzip", url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/Son_Poster.pdf" },
-            ]),
+                Poster { name: "Gavin: Biocompress 1".into(), url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/Saxer_Poster.pdf".into(), ..Default::default() },
+                Poster { name: "Jared: DNAzip".into(), url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/ArroyoRuiz_Poster.pdf".into(), ..Default::default() },
+                Poster { name: "Ryan: DNAzip".into(), url: "https://www.cs.carleton.edu/cs_comps/2526/genezippers_web/website/img/posters/Son_Poster.pdf".into(), ..Default::default() },
+            ].into()),
             photos: None,
+            // Meant to hold a chart of the Huffman, DNAzip and Biocompress 1 compression
+            // ratios from the paper, once those figures are added here.
+            blocks: None,
+            demo: Some("dna-compression".into()),
         },
         Project {
-            id: "portfolio",
-            title: "Portfolio Website",
-            subtitle: "Modern, High-Performance Web Development",
-            tag: "Web App",
-            description: "A high-performance portfolio website built with Rust and Leptos, featuring modern design and seamless navigation.",
-            overview: "Built to demonstrate the capabilities of WebAssembly and Rust in frontend development. This site features fine-grained reactivity, signal-based state management, and type-safe routing. It is fully typed and compiled to WASM for near-native performance.",
-            role: "Full-Stack Developer",
-            technologies: Some(vec!["Rust", "Leptos", "WASM", "SCSS", "Trunk"]),
-            live_link: Some("https://rawleo.github.io/home/"),
-            code_link: Some("https://github.com/Rawleo/home"),
+            id: "portfolio".into(),
+            status: Status::Published,
+            visibility: Visibility::Public,
+            passphrase_hash: None,
+            title: "Portfolio Website".into(),
+            subtitle: "Modern, High-Performance Web Development".into(),
+            tag: "Web App".into(),
+            description: "A high-performance portfolio website built with Rust and Leptos, featuring modern design and seamless navigation.".into(),
+            overview: "Built to demonstrate the capabilities of WebAssembly and Rust in frontend development. This site features fine-grained reactivity, signal-based state management, and type-safe routing. It is fully typed and compiled to WASM for near-native performance.".into(),
+            role: "Full-Stack Developer".into(),
+            technologies: Some(vec!["Rust".into(), "Leptos".into(), "WASM".into(), "SCSS".into(), "Trunk".into()].into()),
+            live_link: Some("https://rawleo.github.io/home/".into()),
+            code_link: Some("https://github.com/Rawleo/home".into()),
             paper_link: None,
+            paper_source: None,
             posters: None,
             photos: None,
+            blocks: None,
+            demo: None,
         },
         Project {
-            id: "protein-calculator",
-            title: "Daily Protein Planner",
-            subtitle: "Calculate Your Daily Protein Intake",
-            tag: "Web App",
-            description: "A React-based tool to calculate daily protein needs and food source distribution.",
-            overview: "A cohesive, single-page web application built with Gatsby (React) and Tailwind CSS that helps users calculate exactly how many grams of specific food sources they need to eat to hit their daily protein goals. It features smart distribution logic and a visual dashboard.",
-            role: "Full-Stack Developer",
-            technologies: Some(vec!["Gatsby", "React", "Tailwind CSS", "GitHub Pages"]),
-            live_link: Some("https://rawleo.github.io/protein-calculator/"),
-            code_link: Some("https://github.com/Rawleo/protein-calculator"),
+            id: "protein-calculator".into(),
+            status: Status::Published,
+            visibility: Visibility::Public,
+            passphrase_hash: None,
+            title: "Daily Protein Planner".into(),
+            subtitle: "Calculate Your Daily Protein Intake".into(),
+            tag: "Web App".into(),
+            description: "A React-based tool to calculate daily protein needs and food source distribution.".into(),
+            overview: "A cohesive, single-page web application built with Gatsby (React) and Tailwind CSS that helps users calculate exactly how many grams of specific food sources they need to eat to hit their daily protein goals. It features smart distribution logic and a visual dashboard.".into(),
+            role: "Full-Stack Developer".into(),
+            technologies: Some(vec!["Gatsby".into(), "React".into(), "Tailwind CSS".into(), "GitHub Pages".into()].into()),
+            live_link: Some("https://rawleo.github.io/protein-calculator/".into()),
+            code_link: Some("https://github.com/Rawleo/protein-calculator".into()),
             paper_link: None,
+            paper_source: None,
             posters: None,
             photos: None,
+            blocks: None,
+            demo: None,
         },
         Project {
-            id: "ats-resume-builder",
-            title: "ATS Resume Builder",
-            subtitle: "Stateless, Privacy-Focused Resume Generator",
-            tag: "Web App",
-            description: "A Go-based web application for generating ATS-compliant resumes in PDF and DOCX formats.",
-            overview: "A stateless web application built with Go, Fiber, and HTMX. It allows users to create professional, ATS-optimized resumes without account creation or data storage. Features include dual export formats (PDF/DOCX) and a dynamic, privacy-first architecture.",
-            role: "Full-Stack Developer",
-            technologies: Some(vec!["Go", "Fiber", "HTMX", "Tailwind CSS", "Chromedp", "Render"]),
-            live_link: Some("https://ats-resume-builder-1194.onrender.com"),
-            code_link: Some("https://github.com/Rawleo/ats-resume-builder"),
+            id: "ats-resume-builder".into(),
+            status: Status::Published,
+            visibility: Visibility::Public,
+            passphrase_hash: None,
+            title: "ATS Resume Builder".into(),
+            subtitle: "Stateless, Privacy-Focused Resume Generator".into(),
+            tag: "Web App".into(),
+            description: "A Go-based web application for generating ATS-compliant resumes in PDF and DOCX formats.".into(),
+            overview: "A stateless web application built with Go, Fiber, and HTMX. It allows users to create professional, ATS-optimized resumes without account creation or data storage. Features include dual export formats (PDF/DOCX) and a dynamic, privacy-first architecture.".into(),
+            role: "Full-Stack Developer".into(),
+            technologies: Some(vec!["Go".into(), "Fiber".into(), "HTMX".into(), "Tailwind CSS".into(), "Chromedp".into(), "Render".into()].into()),
+            live_link: Some("https://ats-resume-builder-1194.onrender.com".into()),
+            code_link: Some("https://github.com/Rawleo/ats-resume-builder".into()),
             paper_link: None,
+            paper_source: None,
             posters: None,
             photos: None,
+            blocks: None,
+            demo: None,
         },
         Project {
-            id: "spotwelder",
-            title: "DIY Spotwelder",
-            subtitle: "Microwave Transformer to Battery Spotwelder",
-            tag: "DIY",
-            description: "Revitalizing a microwave transformer into a battery making powerhouse.",
-            overview: "Built to construct custom designed battery packs for my electrical vehicles.",
-            role: "Builder",
-            technologies: Some(vec!["E & M"]),
+            id: "spotwelder".into(),
+            status: Status::Published,
+            visibility: Visibility::Public,
+            passphrase_hash: None,
+            title: "DIY Spotwelder".into(),
+            subtitle: "Microwave Transformer to Battery Spotwelder".into(),
+            tag: "DIY".into(),
+            description: "Revitalizing a microwave transformer into a battery making powerhouse.".into(),
+            overview: "Built to construct custom designed battery packs for my electrical vehicles.".into(),
+            role: "Builder".into(),
+            technologies: Some(vec!["E & M".into()].into()),
             live_link: None,
             code_link: None,
             paper_link: None,
+            paper_source: None,
             posters: None,
             photos: Some(vec![
-                Photo { url: "images/SpotWelderBare.jpg", caption: "Bare Transformer" },
-                Photo { url: "images/SpotWelderTop.jpg", caption: "Top View" },
-                Photo { url: "images/SpotWelderFront.jpg", caption: "Front View" },
-                Photo { url: "images/CoilRemoval.jpg", caption: "Removing the Secondary Coil" },
-            ]),
+                Photo { url: "images/SpotWelderBare.jpg".into(), caption: "Bare Transformer".into(), alt: "A microwave oven transformer on the workbench before modification".into() },
+                Photo { url: "images/SpotWelderTop.jpg".into(), caption: "Top View".into(), alt: "The spot welder seen from above, showing the transformer inside its case".into() },
+                Photo { url: "images/SpotWelderFront.jpg".into(), caption: "Front View".into(), alt: "Front panel of the homemade spot welder with its switch and welding leads".into() },
+                Photo { url: "images/CoilRemoval.jpg".into(), caption: "Removing the Secondary Coil".into(), alt: "The transformer's secondary winding being cut out of its core".into() },
+            ].into()),
+            blocks: None,
+            demo: Some("battery-pack".into()),
         },
         // Project {
         //     id: "Test Project",
@@ -157,24 +451,33 @@ pub fn get_projects() -> Vec<Project> {
     ]
 }
 
+/// The published blog posts, as shown on the site.
 pub fn get_blogs() -> Vec<Blog> {
-    vec![
-        Blog {
-            id: "010526",
-            title: "Sample",
-            subtitle: "Sample Blog",
-            tag: "Blog Sample",
-            description: "Blog Description",
-            overview: "Blog Overview",
-            live_link: None
-        }
-    ]
+    let now = Utc::now();
+    all_blogs()
+        .into_iter()
+        .filter(|b| b.status.is_listed(now))
+        .collect()
+}
+
+/// Every built-in blog post, including drafts and scheduled ones.
+pub fn all_blogs() -> Vec<Blog> {
+    vec![Blog {
+        id: "010526".into(),
+        status: Status::Published,
+        title: "Sample".into(),
+        subtitle: "Sample Blog".into(),
+        tag: "Blog Sample".into(),
+        description: "Blog Description".into(),
+        overview: "Blog Overview".into(),
+        live_link: None,
+    }]
 }
 
 pub fn get_project_by_id(id: &str) -> Option<Project> {
-    get_projects().into_iter().find(|p| p.id == id)
+    get_projects().into_iter().find(|p| &*p.id == id)
 }
 
 pub fn get_blog_by_id(id: &str) -> Option<Blog> {
-    get_blogs().into_iter().find(|b| b.id == id)
-}
\ No newline at end of file
+    get_blogs().into_iter().find(|b| &*b.id == id)
+}
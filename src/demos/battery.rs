@@ -0,0 +1,237 @@
+//! The spot welder demo: sizes a battery pack from a cell and a target, and draws the
+//! cell layout.
+
+use leptos::prelude::*;
+
+use crate::battery::{
+    design_pack, top_face, Cell, PackDesign, Polarity, Target, OVERHEAD, PRESETS,
+    STRIP_THICKNESS_MM, STRIP_WIDTH_MM,
+};
+
+/// Larger layouts are described but not drawn.
+const MAX_DRAWN_CELLS: usize = 400;
+/// Cell diameter and spacing in the diagram, in SVG units.
+const CELL: f64 = 24.0;
+const GAP: f64 = 4.0;
+const MARGIN: f64 = 24.0;
+
+#[component]
+pub fn BatteryPackCalculator() -> impl IntoView {
+    let cell = RwSignal::new(PRESETS[0].cell());
+    let target_voltage = RwSignal::new(36.0);
+    let target_capacity = RwSignal::new(10.0);
+    let welds_per_terminal = RwSignal::new(4.0);
+    let design = Memo::new(move |_| {
+        let target = Target {
+            voltage: target_voltage.get(),
+            capacity_ah: target_capacity.get(),
+        };
+        let welds = welds_per_terminal.get();
+        design_pack(
+            &cell.get(),
+            &target,
+            if welds >= 1.0 { welds as usize } else { 0 },
+        )
+        .map_err(|err| err.to_string())
+    });
+    let on_preset = move |ev| {
+        if let Some(preset) = event_target_value(&ev)
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| PRESETS.get(index))
+        {
+            cell.set(preset.cell());
+        }
+    };
+    let field = |read: fn(&Cell) -> f64, write: fn(&mut Cell, f64)| {
+        (
+            Signal::derive(move || read(&cell.get())),
+            Callback::new(move |value| cell.update(|cell| write(cell, value))),
+        )
+    };
+    let (capacity, set_capacity) = field(
+        |cell| cell.capacity_mah,
+        |cell, value| cell.capacity_mah = value,
+    );
+    let (voltage, set_voltage) = field(|cell| cell.voltage, |cell, value| cell.voltage = value);
+    let (weight, set_weight) = field(|cell| cell.weight_g, |cell, value| cell.weight_g = value);
+
+    view! {
+        <div class="battery-demo">
+            <div class="battery-inputs">
+                <fieldset>
+                    <legend>"Cell"</legend>
+                    <label>
+                        "Chemistry and size"
+                        <select on:change=on_preset>
+                            {PRESETS.iter().enumerate().map(|(index, preset)| view! {
+                                <option value=index.to_string()>{format!("{} {}", preset.chemistry.label(), preset.name)}</option>
+                            }).collect_view()}
+                        </select>
+                    </label>
+                    <NumberInput label="Capacity" unit="mAh" step=100.0 value=capacity on_change=set_capacity/>
+                    <NumberInput label="Nominal voltage" unit="V" step=0.1 value=voltage on_change=set_voltage/>
+                    <NumberInput label="Weight" unit="g" step=1.0 value=weight on_change=set_weight/>
+                </fieldset>
+                <fieldset>
+                    <legend>"Pack"</legend>
+                    <NumberInput label="Voltage" unit="V" step=1.0 value=target_voltage on_change=Callback::new(move |value| target_voltage.set(value))/>
+                    <NumberInput label="Capacity" unit="Ah" step=0.5 value=target_capacity on_change=Callback::new(move |value| target_capacity.set(value))/>
+                    <NumberInput label="Welds per terminal" unit="" step=1.0 value=welds_per_terminal on_change=Callback::new(move |value| welds_per_terminal.set(value))/>
+                </fieldset>
+            </div>
+            {move || match design.get() {
+                Ok(design) => view! { <Results design cell=cell.get()/> }.into_any(),
+                Err(err) => view! { <p class="battery-error">{format!("Cannot size the pack: {err}.")}</p> }.into_any(),
+            }}
+        </div>
+    }
+}
+
+/// A number field that reports only values that parse.
+#[component]
+fn NumberInput(
+    label: &'static str,
+    unit: &'static str,
+    step: f64,
+    #[prop(into)] value: Signal<f64>,
+    on_change: Callback<f64>,
+) -> impl IntoView {
+    view! {
+        <label>
+            {label}
+            <span class="battery-number">
+                <input
+                    type="number"
+                    min="0"
+                    step=step.to_string()
+                    prop:value=move || value.get().to_string()
+                    on:input=move |ev| {
+                        if let Ok(value) = event_target_value(&ev).parse() {
+                            on_change.run(value);
+                        }
+                    }
+                />
+                {(!unit.is_empty()).then(|| view! { <span class="battery-unit">{unit}</span> })}
+            </span>
+        </label>
+    }
+}
+
+#[component]
+fn Results(design: PackDesign, cell: Cell) -> impl IntoView {
+    let figures = [
+        (
+            format!("{}S{}P", design.series, design.parallel),
+            format!("{} cells", design.cells()),
+        ),
+        (
+            format!("{:.1} V", design.nominal_voltage),
+            format!("nominal, {:.1} V charged", design.full_voltage),
+        ),
+        (
+            format!("{:.1} Ah", design.capacity_ah),
+            format!("{:.0} Wh", design.energy_wh),
+        ),
+        (
+            design.welds.to_string(),
+            format!(
+                "spot welds, {:.1} m of strip",
+                design.strip_length_mm / 1000.0
+            ),
+        ),
+        (
+            format!("{:.2} kg", design.total_weight_g / 1000.0),
+            "estimated weight".to_string(),
+        ),
+    ];
+
+    view! {
+        <div class="metrics-grid battery-results">
+            {figures.into_iter().map(|(value, label)| view! {
+                <div class="metric">
+                    <span class="metric-value">{value}</span>
+                    <span class="metric-label">{label}</span>
+                </div>
+            }).collect_view()}
+        </div>
+        <p class="battery-note">
+            {format!(
+                "Cells {:.2} kg, nickel strip ({STRIP_WIDTH_MM} × {STRIP_THICKNESS_MM} mm) {:.0} g, plus {:.0}% for the BMS, wiring and wrap.",
+                design.cells_weight_g / 1000.0,
+                design.nickel_weight_g,
+                OVERHEAD * 100.0,
+            )}
+        </p>
+        <Layout series=design.series parallel=design.parallel cell/>
+    }
+}
+
+/// The top face of the pack: one column per series group, with the nickel strip that
+/// joins them.
+#[component]
+fn Layout(series: usize, parallel: usize, cell: Cell) -> impl IntoView {
+    let description = format!(
+        "{series} groups of {parallel} {} cells in series, seen from the top; each strip joins the positive ends of one group to the negative ends of the next.",
+        cell.chemistry.label(),
+    );
+    if series * parallel > MAX_DRAWN_CELLS {
+        return view! { <p class="battery-note">{format!("{description} The layout is too large to draw here.")}</p> }.into_any();
+    }
+
+    let (polarities, strips) = top_face(series);
+    let x = |group: usize| MARGIN + group as f64 * (CELL + GAP) + CELL / 2.0;
+    let y = |row: usize| MARGIN + row as f64 * (CELL + GAP) + CELL / 2.0;
+    let width = 2.0 * MARGIN + series as f64 * (CELL + GAP) - GAP;
+    let height = 2.0 * MARGIN + parallel as f64 * (CELL + GAP) - GAP;
+
+    let strips = strips
+        .into_iter()
+        .map(|strip| {
+            let left = x(strip.first) - CELL / 2.0 + 2.0;
+            let label = strip.terminal.map(|terminal| match terminal {
+                Polarity::Positive => "B+",
+                Polarity::Negative => "B−",
+            });
+            view! {
+                <g class="battery-strip">
+                    <rect x=left y=MARGIN - 4.0 width=x(strip.last) - x(strip.first) + CELL - 4.0 height=height - 2.0 * MARGIN + 8.0 rx=3/>
+                    {label.map(|label| view! {
+                        <text class="battery-terminal" x=(x(strip.first) + x(strip.last)) / 2.0 y=MARGIN - 8.0 text-anchor="middle">{label}</text>
+                    })}
+                </g>
+            }
+        })
+        .collect_view();
+    let cells = polarities
+        .into_iter()
+        .enumerate()
+        .flat_map(|(group, polarity)| (0..parallel).map(move |row| (group, row, polarity)))
+        .map(|(group, row, polarity)| {
+            let (class, sign) = match polarity {
+                Polarity::Positive => ("battery-cell battery-cell-positive", "+"),
+                Polarity::Negative => ("battery-cell battery-cell-negative", "−"),
+            };
+            view! {
+                <g class=class>
+                    <circle cx=x(group) cy=y(row) r=CELL / 2.0 - 1.0/>
+                    <text x=x(group) y=y(row) text-anchor="middle" dominant-baseline="central">{sign}</text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <figure class="battery-layout">
+            <svg viewBox=format!("0 0 {width} {height}") role="img" aria-label=description.clone()>
+                {strips}
+                {cells}
+            </svg>
+            <figcaption>
+                {format!("Top face of the {series}S{parallel}P pack.")}
+                {(series % 2 == 1).then_some(" The main positive terminal is on the bottom face.")}
+            </figcaption>
+        </figure>
+    }
+    .into_any()
+}
@@ -0,0 +1,283 @@
+//! The GeneZippers demo: compresses a pasted or uploaded FASTA sequence with Huffman
+//! coding and against a reference, entirely in the browser.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::HtmlInputElement;
+
+use crate::dna::{
+    parse_fasta, FastaRecord, Huffman, ReferenceEncoding, Variant, TEXT_BITS_PER_BASE,
+};
+
+/// Variants listed individually; the rest are counted.
+const LISTED_VARIANTS: usize = 10;
+
+#[derive(Clone)]
+struct Comparison {
+    sequence: FastaRecord,
+    huffman: Huffman,
+    huffman_verified: bool,
+    reference: Option<(FastaRecord, ReferenceEncoding, bool)>,
+}
+
+fn compare(sequence: &str, reference: &str) -> Result<Comparison, String> {
+    let first = |text: &str, what: &str| {
+        parse_fasta(text)
+            .map(|records| {
+                records
+                    .into_iter()
+                    .next()
+                    .expect("parsed records are never empty")
+            })
+            .map_err(|err| format!("{what}: {err}"))
+    };
+    let sequence = first(sequence, "Sequence")?;
+    let huffman = Huffman::build(&sequence.sequence);
+    let huffman_verified = huffman
+        .encode(&sequence.sequence)
+        .and_then(|bits| huffman.decode(&bits))
+        .as_ref()
+        == Some(&sequence.sequence);
+    let reference = match reference.trim() {
+        "" => None,
+        text => {
+            let reference = first(text, "Reference")?;
+            let encoding = ReferenceEncoding::encode(&reference.sequence, &sequence.sequence);
+            let verified = encoding.decode(&reference.sequence) == sequence.sequence;
+            Some((reference, encoding, verified))
+        }
+    };
+    Ok(Comparison {
+        sequence,
+        huffman,
+        huffman_verified,
+        reference,
+    })
+}
+
+#[component]
+pub fn DnaCompressionDemo() -> impl IntoView {
+    let sequence = RwSignal::new(String::new());
+    let reference = RwSignal::new(String::new());
+    let result = RwSignal::new(None::<Result<Comparison, String>>);
+    let run = move || {
+        result.set(Some(compare(
+            &sequence.get_untracked(),
+            &reference.get_untracked(),
+        )))
+    };
+    let load_example = move |_| {
+        let (example_reference, example_sequence) = example();
+        reference.set(example_reference);
+        sequence.set(example_sequence);
+        run();
+    };
+
+    view! {
+        <div class="dna-demo">
+            <div class="dna-inputs">
+                <SequenceInput label="Sequence" text=sequence/>
+                <SequenceInput label="Reference (optional, for DNAzip)" text=reference/>
+            </div>
+            <div class="dna-actions">
+                <button type="button" class="btn btn-primary" on:click=move |_| run()>"Compress"</button>
+                <button type="button" class="btn btn-secondary" on:click=load_example>"Load Example"</button>
+            </div>
+            {move || result.get().map(|result| match result {
+                Ok(comparison) => view! { <Results comparison/> }.into_any(),
+                Err(err) => view! { <p class="dna-error">{err}</p> }.into_any(),
+            })}
+        </div>
+    }
+}
+
+/// A sequence text area, which a picked file fills in.
+#[component]
+fn SequenceInput(label: &'static str, text: RwSignal<String>) -> impl IntoView {
+    let on_file = move |ev: leptos::ev::Event| {
+        let input = ev
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok());
+        let Some(file) = input
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(contents) = wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                text.set(contents.as_string().unwrap_or_default());
+            }
+        });
+    };
+
+    view! {
+        <div class="dna-input">
+            <label>
+                {label}
+                <textarea
+                    rows=8
+                    spellcheck="false"
+                    placeholder=">name\nACGTACGT…"
+                    prop:value=text
+                    on:input=move |ev| text.set(event_target_value(&ev))
+                ></textarea>
+            </label>
+            <input type="file" accept=".fa,.fasta,.fna,.txt" aria-label=format!("Upload {label}") on:change=on_file/>
+        </div>
+    }
+}
+
+#[component]
+fn Results(comparison: Comparison) -> impl IntoView {
+    let Comparison {
+        sequence,
+        huffman,
+        huffman_verified,
+        reference,
+    } = comparison;
+    let bases = sequence.sequence.len();
+    let name = if sequence.name.is_empty() {
+        "Your sequence".to_string()
+    } else {
+        sequence.name.clone()
+    };
+    let codes = huffman
+        .table
+        .iter()
+        .map(|entry| {
+            view! {
+                <tr>
+                    <td><code>{entry.symbol.to_string()}</code></td>
+                    <td>{entry.count}</td>
+                    <td><code>{entry.code.clone()}</code></td>
+                </tr>
+            }
+        })
+        .collect_view();
+
+    let reference = match reference {
+        Some((reference, encoding, verified)) => {
+            let count = |kind: fn(&Variant) -> bool| encoding.variants.iter().filter(|variant| kind(variant)).count();
+            let counts = format!(
+                "{} substitution(s), {} insertion(s), {} deletion(s) against {} ({} bases)",
+                count(|v| matches!(v, Variant::Substitution { .. })),
+                count(|v| matches!(v, Variant::Insertion { .. })),
+                count(|v| matches!(v, Variant::Deletion { .. })),
+                if reference.name.is_empty() { "the reference" } else { &reference.name },
+                reference.sequence.len(),
+            );
+            let more = encoding.variants.len().saturating_sub(LISTED_VARIANTS);
+            let variants = encoding.variants.iter().take(LISTED_VARIANTS).map(|variant| {
+                let (kind, detail) = match variant {
+                    Variant::Substitution { base, .. } => ("Substitution", (*base as char).to_string()),
+                    Variant::Insertion { bases, .. } => ("Insertion", String::from_utf8_lossy(bases).into_owned()),
+                    Variant::Deletion { length, .. } => ("Deletion", format!("{length} base(s)")),
+                };
+                view! {
+                    <tr>
+                        <td>{variant.position() + 1}</td>
+                        <td>{kind}</td>
+                        <td><code>{detail}</code></td>
+                    </tr>
+                }
+            }).collect_view();
+            view! {
+                <Measure bits=encoding.total_bits() bases/>
+                <p class="dna-note">{counts}</p>
+                <Verified verified/>
+                <table class="dna-table">
+                    <thead><tr><th>"Position"</th><th>"Change"</th><th>"Bases"</th></tr></thead>
+                    <tbody>{variants}</tbody>
+                </table>
+                {(more > 0).then(|| view! { <p class="dna-note">{format!("… and {more} more")}</p> })}
+            }
+            .into_any()
+        }
+        None => view! { <p class="dna-note">"Add a reference sequence to see how much smaller the differences are."</p> }.into_any(),
+    };
+
+    view! {
+        <div class="dna-results">
+            <div class="dna-result">
+                <h3>"Original"</h3>
+                <Measure bits=bases * TEXT_BITS_PER_BASE bases/>
+                <p class="dna-note">{format!("{name}: {bases} bases as text, one byte each")}</p>
+            </div>
+            <div class="dna-result">
+                <h3>"Huffman"</h3>
+                <Measure bits=huffman.total_bits() bases/>
+                <p class="dna-note">{format!("Including a {}-bit code table", huffman.table_bits())}</p>
+                <Verified verified=huffman_verified/>
+                <table class="dna-table">
+                    <thead><tr><th>"Base"</th><th>"Count"</th><th>"Code"</th></tr></thead>
+                    <tbody>{codes}</tbody>
+                </table>
+            </div>
+            <div class="dna-result">
+                <h3>"DNAzip (simplified)"</h3>
+                {reference}
+            </div>
+        </div>
+    }
+}
+
+/// Size and bits per base of one encoding.
+#[component]
+fn Measure(bits: usize, bases: usize) -> impl IntoView {
+    let bytes = bits.div_ceil(8);
+    let size = if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    };
+    view! {
+        <div class="dna-measure">
+            <span class="dna-size">{size}</span>
+            <span class="dna-rate">{format!("{:.3} bits/base", bits as f64 / bases.max(1) as f64)}</span>
+        </div>
+    }
+}
+
+#[component]
+fn Verified(verified: bool) -> impl IntoView {
+    match verified {
+        true => view! { <p class="dna-verified">"Decodes back to the original"</p> }.into_any(),
+        false => {
+            view! { <p class="dna-error">"Did not decode back to the original"</p> }.into_any()
+        }
+    }
+}
+
+/// A synthetic reference and a sample that differs from it by a few substitutions, an
+/// insertion and a deletion, as FASTA.
+fn example() -> (String, String) {
+    let mut state: u32 = 0x2545_f491;
+    let reference: Vec<u8> = (0..1200)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b"ACGT"[(state % 4) as usize]
+        })
+        .collect();
+    let mut sample = reference.clone();
+    for position in [150, 420, 800] {
+        sample[position] = if sample[position] == b'A' { b'G' } else { b'A' };
+    }
+    sample.drain(1000..1005);
+    sample.splice(600..600, b"GATTACA".iter().copied());
+
+    let fasta = |name: &str, bases: &[u8]| {
+        let lines: Vec<&str> = bases
+            .chunks(60)
+            .map(|line| std::str::from_utf8(line).expect("bases are ASCII"))
+            .collect();
+        format!(">{name}\n{}\n", lines.join("\n"))
+    };
+    (
+        fasta("example reference (synthetic)", &reference),
+        fasta("example sample (synthetic)", &sample),
+    )
+}
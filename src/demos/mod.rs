@@ -0,0 +1,90 @@
+//! Interactive demos shown on project pages.
+//!
+//! A project names its demo by key in [`Project::demo`](crate::data::Project::demo).
+//! [`DEMOS`] maps each key to the demo's heading and a loader, and [`DemoSection`] shows
+//! the heading but only loads the demo once a visitor opens it. With the `split` feature
+//! each loader is a split point, so `cargo leptos build --split` puts every demo in its own
+//! WASM chunk that is only downloaded on first open.
+
+mod battery;
+mod dna;
+
+use std::future::Future;
+use std::pin::Pin;
+
+use leptos::prelude::*;
+
+use crate::data::Text;
+
+pub use battery::BatteryPackCalculator;
+pub use dna::DnaCompressionDemo;
+
+type DemoView = Pin<Box<dyn Future<Output = AnyView> + Send>>;
+
+/// An entry of the demo registry.
+pub struct Demo {
+    pub key: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    load: fn() -> DemoView,
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo {
+        key: "dna-compression",
+        title: "DNA Compression",
+        summary: "Paste or upload a small FASTA sequence to compress it with Huffman coding and, given a reference, \
+                  with a simplified DNAzip-style encoder that stores only the differences. Everything runs in your browser.",
+        load: || Box::pin(dna_compression()),
+    },
+    Demo {
+        key: "battery-pack",
+        title: "Battery Pack Calculator",
+        summary: "Pick a cell and the pack you need to see how many cells go in series and in parallel, \
+                  how many spot welds that takes and roughly what the pack weighs.",
+        load: || Box::pin(battery_pack()),
+    },
+];
+
+pub fn find(key: &str) -> Option<&'static Demo> {
+    DEMOS.iter().find(|demo| demo.key == key)
+}
+
+#[cfg_attr(feature = "split", lazy)]
+async fn dna_compression() -> AnyView {
+    view! { <DnaCompressionDemo/> }.into_any()
+}
+
+#[cfg_attr(feature = "split", lazy)]
+async fn battery_pack() -> AnyView {
+    view! { <BatteryPackCalculator/> }.into_any()
+}
+
+/// A project's demo section by key, closed until the visitor opens it.
+#[cfg_attr(feature = "islands", island)]
+#[cfg_attr(not(feature = "islands"), component)]
+pub fn DemoSection(demo: Text) -> impl IntoView {
+    let Some(demo) = find(&demo) else {
+        return ().into_any();
+    };
+    let open = RwSignal::new(false);
+    view! {
+        <div class="project-section demo-section">
+            <h2>"Try It: "{demo.title}</h2>
+            <p>{demo.summary}</p>
+            {move || match open.get() {
+                false => view! {
+                    <button type="button" class="btn btn-primary" on:click=move |_| open.set(true)>"Open Demo"</button>
+                }
+                .into_any(),
+                true => view! {
+                    <Suspense fallback=|| view! { <p class="demo-loading">"Loading the demo…"</p> }>
+                        {move || Suspend::new((demo.load)())}
+                    </Suspense>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+    .into_any()
+}
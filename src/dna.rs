@@ -0,0 +1,400 @@
+//! The compression methods compared by the GeneZippers project, small enough to run in
+//! the browser on a pasted sequence.
+//!
+//! - [`Huffman`] codes each base by its frequency in the sequence itself.
+//! - [`ReferenceEncoding`] is a simplified take on DNAzip: it stores only how the
+//!   sequence differs from a reference (substituted bases, insertions and deletions),
+//!   with positions as variable-length integers.
+//!
+//! Sizes are counted in bits of the encoded form, including what a decoder would need
+//! besides the reference, so they can be compared with each other and with the input.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt;
+
+/// Longest sequence the demo accepts, to keep it responsive.
+pub const MAX_BASES: usize = 200_000;
+/// Bits per base of the uncompressed text.
+pub const TEXT_BITS_PER_BASE: usize = 8;
+
+/// One `>name` entry of a FASTA file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FastaRecord {
+    pub name: String,
+    /// Upper-case bases, without line breaks.
+    pub sequence: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FastaError {
+    Empty,
+    /// A character that is not a nucleotide code, at a 1-based line and column.
+    InvalidBase {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    TooLong {
+        bases: usize,
+    },
+}
+
+impl fmt::Display for FastaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastaError::Empty => f.write_str("there is no sequence"),
+            FastaError::InvalidBase {
+                line,
+                column,
+                found,
+            } => {
+                write!(
+                    f,
+                    "`{found}` at line {line}, column {column} is not a nucleotide code"
+                )
+            }
+            FastaError::TooLong { bases } => {
+                write!(
+                    f,
+                    "the sequence has {bases} bases; the demo takes at most {MAX_BASES}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FastaError {}
+
+/// Reads FASTA text, or a bare sequence without a header. Accepts the IUPAC nucleotide
+/// codes in either case; `;` lines are comments.
+pub fn parse_fasta(text: &str) -> Result<Vec<FastaRecord>, FastaError> {
+    let mut records: Vec<FastaRecord> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if let Some(name) = line.strip_prefix('>') {
+            records.push(FastaRecord {
+                name: name.trim().to_string(),
+                sequence: Vec::new(),
+            });
+            continue;
+        }
+        if line.starts_with(';') {
+            continue;
+        }
+        if records.is_empty() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            records.push(FastaRecord {
+                name: String::new(),
+                sequence: Vec::new(),
+            });
+        }
+        let record = records.last_mut().expect("a record was just added");
+        for (column, found) in line.chars().enumerate() {
+            match found.to_ascii_uppercase() {
+                c if c.is_whitespace() => {}
+                c @ ('A' | 'C' | 'G' | 'T' | 'U' | 'N' | 'R' | 'Y' | 'K' | 'M' | 'S' | 'W'
+                | 'B' | 'D' | 'H' | 'V') => record.sequence.push(c as u8),
+                _ => {
+                    return Err(FastaError::InvalidBase {
+                        line: number + 1,
+                        column: column + 1,
+                        found,
+                    })
+                }
+            }
+        }
+        if record.sequence.len() > MAX_BASES {
+            return Err(FastaError::TooLong {
+                bases: record.sequence.len(),
+            });
+        }
+    }
+    records.retain(|record| !record.sequence.is_empty());
+    if records.is_empty() {
+        return Err(FastaError::Empty);
+    }
+    Ok(records)
+}
+
+/// A variable-length integer as DNAzip stores positions: 7 bits per byte, with the
+/// high bit set on every byte but the last.
+pub fn vint_bits(value: usize) -> usize {
+    let mut bytes = 1;
+    let mut rest = value >> 7;
+    while rest > 0 {
+        bytes += 1;
+        rest >>= 7;
+    }
+    bytes * 8
+}
+
+/// One symbol's entry in a Huffman code table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeEntry {
+    pub symbol: char,
+    pub count: usize,
+    /// The code as `0`s and `1`s.
+    pub code: String,
+}
+
+/// A Huffman code built from one sequence's base counts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Huffman {
+    /// By symbol.
+    pub table: Vec<CodeEntry>,
+}
+
+impl Huffman {
+    pub fn build(sequence: &[u8]) -> Self {
+        let mut counts = BTreeMap::new();
+        for &base in sequence {
+            *counts.entry(base).or_insert(0usize) += 1;
+        }
+
+        // Each heap entry is a subtree: its total count, a tie-breaker that keeps the
+        // result independent of heap order, and the symbols below it.
+        let mut heap: BinaryHeap<Reverse<(usize, usize, Vec<u8>)>> = counts
+            .iter()
+            .enumerate()
+            .map(|(order, (&symbol, &count))| Reverse((count, order, vec![symbol])))
+            .collect();
+        let mut codes: BTreeMap<u8, String> = counts
+            .keys()
+            .map(|&symbol| (symbol, String::new()))
+            .collect();
+        if counts.len() == 1 {
+            codes.values_mut().for_each(|code| code.push('0'));
+        }
+        let mut order = counts.len();
+        while heap.len() > 1 {
+            let Reverse((left_count, _, left)) = heap.pop().expect("two subtrees remain");
+            let Reverse((right_count, _, right)) = heap.pop().expect("two subtrees remain");
+            for (bit, symbols) in [('0', &left), ('1', &right)] {
+                for symbol in symbols {
+                    codes
+                        .get_mut(symbol)
+                        .expect("every symbol has a code")
+                        .insert(0, bit);
+                }
+            }
+            heap.push(Reverse((
+                left_count + right_count,
+                order,
+                [left, right].concat(),
+            )));
+            order += 1;
+        }
+
+        let table = codes
+            .into_iter()
+            .map(|(symbol, code)| CodeEntry {
+                symbol: symbol as char,
+                count: counts[&symbol],
+                code,
+            })
+            .collect();
+        Self { table }
+    }
+
+    fn code(&self, symbol: u8) -> Option<&str> {
+        self.table
+            .iter()
+            .find(|entry| entry.symbol == symbol as char)
+            .map(|entry| &*entry.code)
+    }
+
+    /// The encoded sequence as `0`s and `1`s, or `None` if it has a symbol the code
+    /// was not built from.
+    pub fn encode(&self, sequence: &[u8]) -> Option<String> {
+        sequence.iter().map(|&base| self.code(base)).collect()
+    }
+
+    pub fn decode(&self, bits: &str) -> Option<Vec<u8>> {
+        let mut sequence = Vec::new();
+        let mut pending = String::new();
+        for bit in bits.chars() {
+            pending.push(bit);
+            if let Some(entry) = self.table.iter().find(|entry| entry.code == pending) {
+                sequence.push(entry.symbol as u8);
+                pending.clear();
+            }
+        }
+        pending.is_empty().then_some(sequence)
+    }
+
+    /// Bits of the encoded sequence alone.
+    pub fn payload_bits(&self) -> usize {
+        self.table
+            .iter()
+            .map(|entry| entry.count * entry.code.len())
+            .sum()
+    }
+
+    /// Bits of the code table, stored canonically as each symbol and its code length.
+    pub fn table_bits(&self) -> usize {
+        8 + self.table.len() * 16
+    }
+
+    pub fn total_bits(&self) -> usize {
+        self.payload_bits() + self.table_bits()
+    }
+}
+
+/// How a sequence differs from its reference at one place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// The base at `position` of the sequence replaces the reference's.
+    Substitution {
+        position: usize,
+        base: u8,
+    },
+    Insertion {
+        position: usize,
+        bases: Vec<u8>,
+    },
+    /// `length` reference bases are missing before `position` of the sequence.
+    Deletion {
+        position: usize,
+        length: usize,
+    },
+}
+
+impl Variant {
+    pub fn position(&self) -> usize {
+        match self {
+            Variant::Substitution { position, .. }
+            | Variant::Insertion { position, .. }
+            | Variant::Deletion { position, .. } => *position,
+        }
+    }
+}
+
+/// Bases compared after a candidate insertion or deletion to accept it.
+const SYNC_BASES: usize = 8;
+/// Longest insertion or deletion looked for before settling for a substitution.
+const MAX_INDEL: usize = 16;
+
+/// A sequence stored as its differences from a reference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferenceEncoding {
+    pub variants: Vec<Variant>,
+    /// Bits per stored base: 2 for plain `ACGT` sequences, 4 when other codes occur.
+    pub base_bits: usize,
+}
+
+impl ReferenceEncoding {
+    /// Walks both sequences together. At a mismatch, the shortest insertion or deletion
+    /// after which the next bases agree again is taken; otherwise the base is a
+    /// substitution.
+    pub fn encode(reference: &[u8], sequence: &[u8]) -> Self {
+        let agrees = |r: usize, s: usize| {
+            if r > reference.len() || s > sequence.len() {
+                return false;
+            }
+            let length = SYNC_BASES.min(reference.len() - r).min(sequence.len() - s);
+            if length == 0 {
+                return r == reference.len() && s == sequence.len();
+            }
+            reference[r..r + length] == sequence[s..s + length]
+        };
+
+        let mut variants = Vec::new();
+        let (mut r, mut s) = (0, 0);
+        while r < reference.len() && s < sequence.len() {
+            if reference[r] == sequence[s] {
+                r += 1;
+                s += 1;
+                continue;
+            }
+            let indel = (1..=MAX_INDEL).find_map(|length| {
+                if agrees(r + length, s) {
+                    Some(Variant::Deletion {
+                        position: s,
+                        length,
+                    })
+                } else if agrees(r, s + length) {
+                    Some(Variant::Insertion {
+                        position: s,
+                        bases: sequence[s..s + length].to_vec(),
+                    })
+                } else {
+                    None
+                }
+            });
+            let variant = indel.unwrap_or(Variant::Substitution {
+                position: s,
+                base: sequence[s],
+            });
+            match &variant {
+                Variant::Substitution { .. } => {
+                    r += 1;
+                    s += 1;
+                }
+                Variant::Insertion { bases, .. } => s += bases.len(),
+                Variant::Deletion { length, .. } => r += length,
+            }
+            variants.push(variant);
+        }
+        if s < sequence.len() {
+            variants.push(Variant::Insertion {
+                position: s,
+                bases: sequence[s..].to_vec(),
+            });
+        } else if r < reference.len() {
+            variants.push(Variant::Deletion {
+                position: s,
+                length: reference.len() - r,
+            });
+        }
+
+        let plain = |base: &u8| b"ACGT".contains(base);
+        let base_bits = if sequence.iter().all(plain) { 2 } else { 4 };
+        Self {
+            variants,
+            base_bits,
+        }
+    }
+
+    /// Rebuilds the sequence from the reference.
+    pub fn decode(&self, reference: &[u8]) -> Vec<u8> {
+        let mut sequence = Vec::with_capacity(reference.len());
+        let mut r = 0;
+        for variant in &self.variants {
+            let copy = variant.position() - sequence.len();
+            sequence.extend_from_slice(&reference[r..r + copy]);
+            r += copy;
+            match variant {
+                Variant::Substitution { base, .. } => {
+                    sequence.push(*base);
+                    r += 1;
+                }
+                Variant::Insertion { bases, .. } => sequence.extend_from_slice(bases),
+                Variant::Deletion { length, .. } => r += length,
+            }
+        }
+        sequence.extend_from_slice(&reference[r.min(reference.len())..]);
+        sequence
+    }
+
+    /// A header with the variant count and the base width, then per variant a 2-bit
+    /// kind, the distance from the previous variant, and its bases or length.
+    pub fn total_bits(&self) -> usize {
+        let mut bits = vint_bits(self.variants.len()) + 8;
+        let mut previous = 0;
+        for variant in &self.variants {
+            bits += 2 + vint_bits(variant.position() - previous);
+            bits += match variant {
+                Variant::Substitution { .. } => self.base_bits,
+                Variant::Insertion { bases, .. } => {
+                    vint_bits(bases.len()) + bases.len() * self.base_bits
+                }
+                Variant::Deletion { length, .. } => vint_bits(*length),
+            };
+            previous = variant.position();
+        }
+        bits
+    }
+}
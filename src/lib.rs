@@ -1,10 +1,29 @@
+#![recursion_limit = "512"]
+
+pub mod admin;
 pub mod app;
+pub mod auth;
+pub mod battery;
+pub mod blocks;
+pub mod chart;
+#[cfg(feature = "ssr")]
+pub mod config;
+pub mod content;
 pub mod data;
+pub mod demos;
+pub mod dna;
+#[cfg(feature = "linkcheck")]
+pub mod links;
+#[cfg(feature = "mirror")]
+pub mod mirror;
+pub mod routing;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
-    use crate::app::*;
     console_error_panic_hook::set_once();
-    leptos::mount::hydrate_body(App);
+    #[cfg(feature = "islands")]
+    leptos::mount::hydrate_islands();
+    #[cfg(not(feature = "islands"))]
+    leptos::mount::hydrate_lazy(crate::app::App);
 }
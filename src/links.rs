@@ -0,0 +1,372 @@
+//! Checking the external links in projects and blog posts, for `check-links`.
+//!
+//! Every `live_link`, `code_link`, `paper_link` and poster URL that points off-site is
+//! requested, a few at a time, and the outcome stored in a [`LinkCache`] on disk. Links
+//! that worked recently are not requested again until their result is older than
+//! [`CheckOptions::max_age`], and an offline run reports purely from the cache.
+//! Links that failed are always checked again when online, so a fixed link shows up
+//! as fixed straight away.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::{Client, Method, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::data::{Blog, Project, Text};
+
+/// An external URL and every field that links to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub url: Text,
+    /// Where the URL is used, e.g. `projects[0] (genezippers).paper_link`.
+    pub locations: Vec<String>,
+}
+
+/// The absolute http(s) links in `projects` and `blogs`, each once, in the order they
+/// first appear. Relative links point at the site's own files, which `portfolio lint`
+/// checks instead.
+pub fn collect_links(projects: &[Project], blogs: &[Blog]) -> Vec<Link> {
+    let mut links: Vec<Link> = Vec::new();
+    let mut add = |url: &Text, location: String| {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return;
+        }
+        match links.iter_mut().find(|link| link.url == *url) {
+            Some(link) => link.locations.push(location),
+            None => links.push(Link {
+                url: url.clone(),
+                locations: vec![location],
+            }),
+        }
+    };
+
+    for (index, project) in projects.iter().enumerate() {
+        let at = format!("projects[{index}] ({})", project.id);
+        for (field, link) in [
+            ("live_link", &project.live_link),
+            ("code_link", &project.code_link),
+            ("paper_link", &project.paper_link),
+        ] {
+            if let Some(url) = link {
+                add(url, format!("{at}.{field}"));
+            }
+        }
+        for (i, poster) in project
+            .posters
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            add(&poster.url, format!("{at}.posters[{i}].url"));
+        }
+    }
+    for (index, blog) in blogs.iter().enumerate() {
+        if let Some(url) = &blog.live_link {
+            add(url, format!("blogs[{index}] ({}).live_link", blog.id));
+        }
+    }
+    links
+}
+
+/// What requesting a link led to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum LinkStatus {
+    /// A successful response, possibly after following redirects to `redirected_to`.
+    Ok {
+        status: u16,
+        redirected_to: Option<String>,
+    },
+    /// The server answered with an error status.
+    Broken { status: u16 },
+    /// No answer: the request failed or timed out.
+    Failed { error: String },
+    /// Offline, with nothing in the cache.
+    Unchecked,
+}
+
+impl LinkStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, LinkStatus::Ok { .. })
+    }
+}
+
+impl fmt::Display for LinkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkStatus::Ok {
+                status,
+                redirected_to: None,
+            } => write!(f, "ok ({status})"),
+            LinkStatus::Ok {
+                status,
+                redirected_to: Some(to),
+            } => write!(f, "ok ({status}), redirected to {to}"),
+            LinkStatus::Broken { status } => write!(f, "broken ({status})"),
+            LinkStatus::Failed { error } => write!(f, "failed: {error}"),
+            LinkStatus::Unchecked => f.write_str("not checked (offline and not cached)"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    checked_at: DateTime<Utc>,
+    status: LinkStatus,
+}
+
+/// Earlier results by URL, kept as JSON between runs.
+#[derive(Debug, Default)]
+pub struct LinkCache {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+impl LinkCache {
+    /// Reads the cache at `path`, starting empty if there is no file yet.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {err}", path.display()),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            path: Some(path),
+            entries,
+        })
+    }
+
+    /// A cache that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Writes the cache back to where it was loaded from.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(&self.entries)?)
+    }
+
+    /// The cached result for `url`, if it can be reused at `now`.
+    fn fresh(&self, url: &str, now: DateTime<Utc>, options: &CheckOptions) -> Option<&CacheEntry> {
+        let entry = self.entries.get(url)?;
+        let reusable =
+            options.offline || (entry.status.is_ok() && now - entry.checked_at < options.max_age);
+        reusable.then_some(entry)
+    }
+}
+
+/// How links are checked.
+#[derive(Clone, Debug)]
+pub struct CheckOptions {
+    /// How many requests may be in flight at once.
+    pub concurrency: usize,
+    /// How long a single request may take, redirects included.
+    pub timeout: Duration,
+    /// How long a working link's result is reused before it is checked again.
+    pub max_age: chrono::Duration,
+    /// Report from the cache only, without any requests.
+    pub offline: bool,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            timeout: Duration::from_secs(15),
+            max_age: chrono::Duration::hours(24),
+            offline: false,
+        }
+    }
+}
+
+/// The outcome for one link.
+#[derive(Clone, Debug)]
+pub struct LinkResult {
+    pub link: Link,
+    pub status: LinkStatus,
+    /// When the status was found, or `None` if the link could not be checked.
+    pub checked_at: Option<DateTime<Utc>>,
+    /// Whether the status came from the cache rather than a request made by this run.
+    pub cached: bool,
+}
+
+/// Results for every link, in the order the links were given.
+#[derive(Clone, Debug, Default)]
+pub struct LinkReport {
+    pub results: Vec<LinkResult>,
+}
+
+impl LinkReport {
+    /// Links that are broken, failed or could not be checked.
+    pub fn problems(&self) -> impl Iterator<Item = &LinkResult> {
+        self.results.iter().filter(|result| !result.status.is_ok())
+    }
+}
+
+impl fmt::Display for LinkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            let marker = if result.status.is_ok() {
+                "ok  "
+            } else {
+                "FAIL"
+            };
+            write!(f, "{marker} {}: {}", result.link.url, result.status)?;
+            if let (true, Some(at)) = (result.cached, result.checked_at) {
+                write!(f, " (cached from {})", at.format("%Y-%m-%d %H:%M UTC"))?;
+            }
+            writeln!(f)?;
+            if !result.status.is_ok() {
+                for location in &result.link.locations {
+                    writeln!(f, "       used in {location}")?;
+                }
+            }
+        }
+        let problems = self.problems().count();
+        write!(
+            f,
+            "{} link(s) checked, {problems} with problems",
+            self.results.len()
+        )
+    }
+}
+
+/// Checks `links`, reusing and updating `cache`. The cache is only changed in memory;
+/// call [`LinkCache::save`] to keep the results.
+pub async fn check_links(
+    links: Vec<Link>,
+    cache: &mut LinkCache,
+    options: &CheckOptions,
+) -> Result<LinkReport, reqwest::Error> {
+    let client = Client::builder()
+        .timeout(options.timeout)
+        .user_agent(concat!("portfolio-check-links/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let now = Utc::now();
+
+    let mut results: Vec<Option<LinkResult>> = vec![None; links.len()];
+    let mut pending = Vec::new();
+    for (index, link) in links.into_iter().enumerate() {
+        if let Some(entry) = cache.fresh(&link.url, now, options) {
+            results[index] = Some(LinkResult {
+                status: entry.status.clone(),
+                checked_at: Some(entry.checked_at),
+                cached: true,
+                link,
+            });
+        } else if options.offline {
+            results[index] = Some(LinkResult {
+                link,
+                status: LinkStatus::Unchecked,
+                checked_at: None,
+                cached: false,
+            });
+        } else {
+            pending.push((index, link));
+        }
+    }
+
+    let checked: Vec<_> = stream::iter(pending)
+        .map(|(index, link)| {
+            let client = &client;
+            async move {
+                let status = request(client, &link.url).await;
+                (index, link, status)
+            }
+        })
+        .buffer_unordered(options.concurrency.max(1))
+        .collect()
+        .await;
+
+    let checked_at = Utc::now();
+    for (index, link, status) in checked {
+        cache.entries.insert(
+            link.url.to_string(),
+            CacheEntry {
+                checked_at,
+                status: status.clone(),
+            },
+        );
+        results[index] = Some(LinkResult {
+            link,
+            status,
+            checked_at: Some(checked_at),
+            cached: false,
+        });
+    }
+
+    Ok(LinkReport {
+        results: results.into_iter().flatten().collect(),
+    })
+}
+
+/// Tries a `HEAD` request first, then `GET` for servers that answer `HEAD` with an error.
+async fn request(client: &Client, url: &str) -> LinkStatus {
+    let mut status = send(client, Method::HEAD, url).await;
+    if !status.is_ok() {
+        status = send(client, Method::GET, url).await;
+    }
+    status
+}
+
+async fn send(client: &Client, method: Method, url: &str) -> LinkStatus {
+    match client.request(method, url).send().await {
+        Ok(response) => {
+            let status = response.status();
+            if status.is_success() {
+                let to = response.url();
+                LinkStatus::Ok {
+                    status: status.as_u16(),
+                    redirected_to: (Url::parse(url).ok().as_ref() != Some(to))
+                        .then(|| to.to_string()),
+                }
+            } else {
+                LinkStatus::Broken {
+                    status: status.as_u16(),
+                }
+            }
+        }
+        Err(err) if err.is_timeout() => LinkStatus::Failed {
+            error: "timed out".to_string(),
+        },
+        Err(err) => LinkStatus::Failed {
+            error: error_chain(&err.without_url()),
+        },
+    }
+}
+
+/// reqwest's own message is just "error sending request"; the cause says what happened.
+pub(crate) fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
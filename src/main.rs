@@ -1,3 +1,4 @@
+#![recursion_limit = "512"]
 
 #[cfg(feature = "ssr")]
 #[tokio::main]
@@ -7,6 +8,22 @@ async fn main() {
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use portfolio::app::*;
+    use portfolio::auth::ssr::{hash_password, AuthState};
+    use portfolio::config::SiteConfig;
+    use portfolio::content::{AccessSigner, Content, MediaStore, MEDIA_URL_DIR};
+    use tower_http::services::ServeDir;
+
+    // `portfolio-bin hash-password` reads a password from stdin and prints the value
+    // to use for PORTFOLIO_ADMIN_PASSWORD_HASH.
+    if std::env::args().nth(1).as_deref() == Some("hash-password") {
+        let mut password = String::new();
+        std::io::stdin().read_line(&mut password).unwrap();
+        println!(
+            "{}",
+            hash_password(password.trim_end_matches(['\r', '\n'])).unwrap()
+        );
+        return;
+    }
 
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
@@ -14,11 +31,44 @@ async fn main() {
     // Generate the list of routes in your Leptos App
     let routes = generate_route_list(App);
 
+    let site_config = SiteConfig::from_env();
+    let auth = AuthState::new(site_config.auth);
+    if !auth.is_configured() {
+        log!("admin login disabled: PORTFOLIO_ADMIN_USERNAME / PORTFOLIO_ADMIN_PASSWORD_HASH not set");
+    }
+
+    let content = Content::from_config(&site_config.content).unwrap_or_else(|err| {
+        panic!(
+            "could not open content backend {:?}: {err}",
+            site_config.content
+        )
+    });
+
+    if site_config.links.secret.is_none() {
+        log!(
+            "PORTFOLIO_LINK_SECRET not set: preview and invite links will stop working on restart"
+        );
+    }
+    let access = AccessSigner::new(&site_config.links);
+    let media = MediaStore::new(&site_config.media);
+    let media_files = ServeDir::new(media.dir());
+
     let app = Router::new()
-        .leptos_routes(&leptos_options, routes, {
-            let leptos_options = leptos_options.clone();
-            move || shell(leptos_options.clone())
-        })
+        .leptos_routes_with_context(
+            &leptos_options,
+            routes,
+            move || {
+                provide_context(auth.clone());
+                provide_context(content.clone());
+                provide_context(access.clone());
+                provide_context(media.clone());
+            },
+            {
+                let leptos_options = leptos_options.clone();
+                move || shell(leptos_options.clone())
+            },
+        )
+        .nest_service(&format!("/{MEDIA_URL_DIR}"), media_files)
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptos_options);
 
@@ -33,9 +83,10 @@ async fn main() {
 
 #[cfg(not(feature = "ssr"))]
 pub fn main() {
-    use portfolio::app::*;
     use leptos::prelude::*;
+    use portfolio::app::*;
     _ = console_log::init_with_level(log::Level::Debug);
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
     console_error_panic_hook::set_once();
     mount_to_body(App);
 }
@@ -0,0 +1,420 @@
+//! Local copies of the poster PDFs and papers that projects link to, for `portfolio mirror`.
+//!
+//! Each external `Poster.url` and `paper_link` that is a PDF is downloaded into a store
+//! directory, kept in the repository so that a build still has the file after the original
+//! host is gone. Anything else, like a paper published as a web page, would lose its
+//! stylesheets, images and relative links in a copy, so it stays an external link.
+//! The store's manifest records every file's SHA-256; when the upstream file changes, the
+//! stored copy is kept and the change reported until it is accepted. The stored files are
+//! then copied into the site output under [`MIRROR_URL_DIR`] and the links rewritten to
+//! them, with the original URL kept as the link's source.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::data::{Project, Text};
+use crate::links::error_chain;
+
+/// Where mirrored files are served, relative to the site's base path.
+pub const MIRROR_URL_DIR: &str = "mirror";
+/// The store's record of what it holds.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// A file in the store.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirroredFile {
+    pub file: String,
+    pub sha256: String,
+    pub size: u64,
+    pub fetched_at: DateTime<Utc>,
+    /// When the upstream file was last compared with this copy.
+    pub checked_at: DateTime<Utc>,
+}
+
+/// The store's contents by upstream URL.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    dir: PathBuf,
+    files: BTreeMap<String, MirroredFile>,
+}
+
+impl Manifest {
+    pub fn load(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        let files = match fs::read(dir.join(MANIFEST_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {err}", dir.join(MANIFEST_FILE).display()),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self { dir, files })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.dir.join(MANIFEST_FILE),
+            serde_json::to_vec_pretty(&self.files)?,
+        )
+    }
+
+    pub fn get(&self, url: &str) -> Option<&MirroredFile> {
+        self.files.get(url)
+    }
+
+    fn store(&mut self, url: &str, bytes: &[u8], now: DateTime<Utc>) -> io::Result<()> {
+        let file = file_name(url);
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(&file), bytes)?;
+        self.files.insert(
+            url.to_string(),
+            MirroredFile {
+                file,
+                sha256: sha256(bytes),
+                size: bytes.len() as u64,
+                fetched_at: now,
+                checked_at: now,
+            },
+        );
+        Ok(())
+    }
+}
+
+/// How mirroring goes about it.
+#[derive(Clone, Debug)]
+pub struct MirrorOptions {
+    pub timeout: Duration,
+    /// Replace stored copies whose upstream file has changed, instead of only reporting it.
+    pub accept_changes: bool,
+    /// Use the stored copies without contacting the upstream hosts.
+    pub offline: bool,
+}
+
+impl Default for MirrorOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            accept_changes: false,
+            offline: false,
+        }
+    }
+}
+
+/// What happened to one upstream file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MirrorOutcome {
+    /// Downloaded for the first time.
+    Added,
+    /// Same as the stored copy.
+    Unchanged,
+    /// Not compared with upstream, because the run was offline.
+    Stored,
+    /// Upstream differs from the stored copy, which is still the one used.
+    Changed { stored: String, upstream: String },
+    /// Upstream differed and the stored copy was replaced.
+    Updated { previous: String },
+    /// Upstream could not be fetched; the stored copy is used.
+    Unavailable { error: String },
+    /// Upstream could not be fetched and there is no copy, so the link stays external.
+    Missing { error: String },
+    /// Upstream is not a PDF, so the link stays external.
+    NotPdf,
+}
+
+impl MirrorOutcome {
+    /// Whether the outcome needs someone to look at it.
+    pub fn is_flagged(&self) -> bool {
+        matches!(
+            self,
+            MirrorOutcome::Changed { .. }
+                | MirrorOutcome::Unavailable { .. }
+                | MirrorOutcome::Missing { .. }
+        )
+    }
+}
+
+impl fmt::Display for MirrorOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorOutcome::Added => f.write_str("downloaded"),
+            MirrorOutcome::Unchanged => f.write_str("unchanged"),
+            MirrorOutcome::Stored => f.write_str("using the stored copy (offline)"),
+            MirrorOutcome::Changed { stored, upstream } => write!(
+                f,
+                "CHANGED upstream (stored {}, upstream {}); still using the stored copy",
+                short(stored),
+                short(upstream)
+            ),
+            MirrorOutcome::Updated { previous } => write!(f, "updated (was {})", short(previous)),
+            MirrorOutcome::Unavailable { error } => {
+                write!(f, "upstream unavailable, using the stored copy: {error}")
+            }
+            MirrorOutcome::Missing { error } => {
+                write!(f, "NOT MIRRORED, left as an external link: {error}")
+            }
+            MirrorOutcome::NotPdf => f.write_str("not a PDF, left as an external link"),
+        }
+    }
+}
+
+fn short(sha256: &str) -> &str {
+    &sha256[..sha256.len().min(12)]
+}
+
+/// One upstream file and what happened to it.
+#[derive(Clone, Debug)]
+pub struct MirrorResult {
+    pub url: String,
+    pub outcome: MirrorOutcome,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MirrorReport {
+    pub results: Vec<MirrorResult>,
+}
+
+impl MirrorReport {
+    pub fn flagged(&self) -> impl Iterator<Item = &MirrorResult> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome.is_flagged())
+    }
+}
+
+impl fmt::Display for MirrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            writeln!(f, "{}: {}", result.url, result.outcome)?;
+        }
+        let flagged = self.flagged().count();
+        write!(
+            f,
+            "{} upstream file(s), {flagged} flagged",
+            self.results.len()
+        )
+    }
+}
+
+/// The external poster and paper URLs of `projects`, with a mirrored link's source
+/// standing in for its local URL, so running again after a rewrite finds the same files.
+pub fn upstream_urls(projects: &[Project]) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for project in projects {
+        let posters = project.posters.as_deref().unwrap_or_default();
+        let posters = posters
+            .iter()
+            .map(|poster| poster.source.as_ref().unwrap_or(&poster.url));
+        let paper = project
+            .paper_source
+            .as_ref()
+            .or(project.paper_link.as_ref());
+        for url in posters.chain(paper) {
+            if is_external(url) && !urls.iter().any(|known| known == &**url) {
+                urls.push(url.to_string());
+            }
+        }
+    }
+    urls
+}
+
+/// Brings the store up to date with the upstream files of `projects`.
+pub async fn update_store(
+    projects: &[Project],
+    manifest: &mut Manifest,
+    options: &MirrorOptions,
+) -> Result<MirrorReport, reqwest::Error> {
+    let client = Client::builder()
+        .timeout(options.timeout)
+        .user_agent(concat!("portfolio-mirror/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+
+    let mut report = MirrorReport::default();
+    for url in upstream_urls(projects) {
+        let outcome = if options.offline {
+            match manifest.get(&url) {
+                Some(_) => MirrorOutcome::Stored,
+                None => MirrorOutcome::Missing {
+                    error: "offline".to_string(),
+                },
+            }
+        } else {
+            match download(&client, &url).await {
+                Ok(bytes) if !is_pdf(&bytes) => match manifest.get(&url) {
+                    Some(_) => MirrorOutcome::Unavailable {
+                        error: "upstream is no longer a PDF".to_string(),
+                    },
+                    None => MirrorOutcome::NotPdf,
+                },
+                Ok(bytes) => compare(manifest, &url, &bytes, options).unwrap_or_else(|err| {
+                    MirrorOutcome::Missing {
+                        error: err.to_string(),
+                    }
+                }),
+                Err(error) if manifest.get(&url).is_some() => MirrorOutcome::Unavailable { error },
+                Err(error) => MirrorOutcome::Missing { error },
+            }
+        };
+        report.results.push(MirrorResult { url, outcome });
+    }
+    Ok(report)
+}
+
+fn compare(
+    manifest: &mut Manifest,
+    url: &str,
+    bytes: &[u8],
+    options: &MirrorOptions,
+) -> io::Result<MirrorOutcome> {
+    let now = Utc::now();
+    let upstream = sha256(bytes);
+    let Some(stored) = manifest.files.get_mut(url) else {
+        manifest.store(url, bytes, now)?;
+        return Ok(MirrorOutcome::Added);
+    };
+    if stored.sha256 == upstream {
+        stored.checked_at = now;
+        return Ok(MirrorOutcome::Unchanged);
+    }
+    if !options.accept_changes {
+        stored.checked_at = now;
+        return Ok(MirrorOutcome::Changed {
+            stored: stored.sha256.clone(),
+            upstream,
+        });
+    }
+    let previous = stored.sha256.clone();
+    manifest.store(url, bytes, now)?;
+    Ok(MirrorOutcome::Updated { previous })
+}
+
+async fn download(client: &Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| error_chain(&err.without_url()))?;
+    if !response.status().is_success() {
+        return Err(format!("the server answered {}", response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|err| error_chain(&err.without_url()))?;
+    Ok(bytes.to_vec())
+}
+
+/// Copies every stored file used by `projects` into `site_dir`/[`MIRROR_URL_DIR`] and
+/// points their links at the copies. Links without a stored copy are left as they are,
+/// and a stored file that no longer matches its recorded SHA-256 is an error.
+pub fn publish(projects: &mut [Project], manifest: &Manifest, site_dir: &Path) -> io::Result<()> {
+    let out = site_dir.join(MIRROR_URL_DIR);
+    fs::create_dir_all(&out)?;
+    let local = |url: &Text| -> io::Result<Option<Text>> {
+        let Some(mirrored) = manifest.get(url) else {
+            return Ok(None);
+        };
+        let stored = manifest.dir.join(&mirrored.file);
+        let bytes = fs::read(&stored)?;
+        if sha256(&bytes) != mirrored.sha256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} does not match the SHA-256 in {MANIFEST_FILE}",
+                    stored.display()
+                ),
+            ));
+        }
+        fs::write(out.join(&mirrored.file), bytes)?;
+        Ok(Some(format!("{MIRROR_URL_DIR}/{}", mirrored.file).into()))
+    };
+
+    for project in projects {
+        if let Some(posters) = &project.posters {
+            let mut posters = posters.to_vec();
+            for poster in &mut posters {
+                let source = poster.source.clone().unwrap_or_else(|| poster.url.clone());
+                if let Some(url) = local(&source)? {
+                    poster.url = url;
+                    poster.source = Some(source);
+                }
+            }
+            project.posters = Some(posters.into());
+        }
+        if let Some(source) = project
+            .paper_source
+            .clone()
+            .or_else(|| project.paper_link.clone())
+        {
+            if let Some(url) = local(&source)? {
+                project.paper_link = Some(url);
+                project.paper_source = Some(source);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_pdf(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"%PDF-")
+}
+
+fn is_external(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// A stable, readable name for the copy of `url`: the last path segment, made safe,
+/// with part of the URL's hash so equal names from different places do not collide.
+fn file_name(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let last = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let (stem, extension) = match last.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && extension.len() <= 5 => (stem, extension),
+        _ => (last, "pdf"),
+    };
+    let safe = |text: &str| -> String {
+        text.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    };
+    let stem: String = safe(stem).chars().take(48).collect();
+    let stem = if stem.is_empty() {
+        "file".to_string()
+    } else {
+        stem
+    };
+    format!(
+        "{stem}-{}.{}",
+        short(&sha256(url.as_bytes())),
+        safe(&extension.to_ascii_lowercase())
+    )
+}
@@ -0,0 +1,219 @@
+//! The site's pages and the base path they are served under.
+//!
+//! Links are built from a [`Route`] rather than spelled out, and [`Route::parse`] reads the
+//! paths the router sees back into one. The CSR build on GitHub Pages lives under `/home`,
+//! while the server build is always mounted at the site root. The base path is fixed at
+//! build time through the `PORTFOLIO_BASE_PATH` environment variable, and [`BasePath`] turns
+//! route paths into links and the browser's paths back into route paths.
+
+use crate::content::ContentKind;
+use crate::data::Text;
+
+/// A page of the site. Each one but [`Route::Section`] has its own entry in the `Routes`
+/// table in [`App`](crate::app::App), with the path [`Route::pattern`] gives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Route {
+    Home,
+    /// A section of the home page.
+    Section(Section),
+    Projects,
+    Project(Text),
+    Blog,
+    BlogPost(Text),
+    Photos,
+    About,
+    AdminLogin,
+    Admin,
+    AdminProjects,
+    /// A project's editor, or [`NEW_ID`](crate::admin::NEW_ID)'s for a new one.
+    AdminProject(Text),
+    AdminProjectHistory(Text),
+    AdminBlogs,
+    AdminBlog(Text),
+    AdminBlogHistory(Text),
+    AdminPhotos,
+    AdminMedia,
+    AdminAudit,
+}
+
+impl Route {
+    /// The public page of a project or blog post.
+    pub fn content(kind: ContentKind, id: impl Into<Text>) -> Self {
+        match kind {
+            ContentKind::Project => Route::Project(id.into()),
+            ContentKind::Blog => Route::BlogPost(id.into()),
+        }
+    }
+
+    /// The admin editor of a project or blog post.
+    pub fn editor(kind: ContentKind, id: impl Into<Text>) -> Self {
+        match kind {
+            ContentKind::Project => Route::AdminProject(id.into()),
+            ContentKind::Blog => Route::AdminBlog(id.into()),
+        }
+    }
+
+    /// The revision history of a project or blog post.
+    pub fn history(kind: ContentKind, id: impl Into<Text>) -> Self {
+        match kind {
+            ContentKind::Project => Route::AdminProjectHistory(id.into()),
+            ContentKind::Blog => Route::AdminBlogHistory(id.into()),
+        }
+    }
+
+    /// The path below the base path.
+    pub fn path(&self) -> String {
+        match self {
+            Route::Section(section) => format!("/#{}", section.id()),
+            Route::Project(id) => format!("/project/{id}"),
+            Route::BlogPost(id) => format!("/blog/{id}"),
+            Route::AdminProject(id) => format!("/admin/projects/{id}"),
+            Route::AdminProjectHistory(id) => format!("/admin/projects/{id}/history"),
+            Route::AdminBlog(id) => format!("/admin/blogs/{id}"),
+            Route::AdminBlogHistory(id) => format!("/admin/blogs/{id}/history"),
+            route => route.pattern().to_string(),
+        }
+    }
+
+    /// The link to the page.
+    pub fn href(&self, base: &BasePath) -> String {
+        base.path(&self.path())
+    }
+
+    /// The path of the page's entry in the `Routes` table.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Route::Home | Route::Section(_) => "/",
+            Route::Projects => "/projects",
+            Route::Project(_) => "/project/:id",
+            Route::Blog => "/blog",
+            Route::BlogPost(_) => "/blog/:id",
+            Route::Photos => "/photos",
+            Route::About => "/about",
+            Route::AdminLogin => "/admin/login",
+            Route::Admin => "/admin",
+            Route::AdminProjects => "/admin/projects",
+            Route::AdminProject(_) => "/admin/projects/:id",
+            Route::AdminProjectHistory(_) => "/admin/projects/:id/history",
+            Route::AdminBlogs => "/admin/blogs",
+            Route::AdminBlog(_) => "/admin/blogs/:id",
+            Route::AdminBlogHistory(_) => "/admin/blogs/:id/history",
+            Route::AdminPhotos => "/admin/photos",
+            Route::AdminMedia => "/admin/media",
+            Route::AdminAudit => "/admin/audit",
+        }
+    }
+
+    /// The page at `path`, below the base path. A query is ignored, and so is a fragment
+    /// anywhere but on the home page, where it names the section.
+    pub fn parse(path: &str) -> Option<Self> {
+        let (path, fragment) = path
+            .split_once('#')
+            .map_or((path, None), |(path, fragment)| (path, Some(fragment)));
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        let segments: Vec<&str> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let route = match segments.as_slice() {
+            [] => fragment
+                .and_then(Section::from_id)
+                .map_or(Route::Home, Route::Section),
+            ["projects"] => Route::Projects,
+            ["project", id] => Route::Project((*id).into()),
+            ["blog"] => Route::Blog,
+            ["blog", id] => Route::BlogPost((*id).into()),
+            ["photos"] => Route::Photos,
+            ["about"] => Route::About,
+            ["admin", "login"] => Route::AdminLogin,
+            ["admin"] => Route::Admin,
+            ["admin", "projects"] => Route::AdminProjects,
+            ["admin", "projects", id] => Route::AdminProject((*id).into()),
+            ["admin", "projects", id, "history"] => Route::AdminProjectHistory((*id).into()),
+            ["admin", "blogs"] => Route::AdminBlogs,
+            ["admin", "blogs", id] => Route::AdminBlog((*id).into()),
+            ["admin", "blogs", id, "history"] => Route::AdminBlogHistory((*id).into()),
+            ["admin", "photos"] => Route::AdminPhotos,
+            ["admin", "media"] => Route::AdminMedia,
+            ["admin", "audit"] => Route::AdminAudit,
+            _ => return None,
+        };
+        Some(route)
+    }
+}
+
+/// The sections of the home page the navbar links to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    Home,
+    Projects,
+    Blogs,
+    Photos,
+}
+
+impl Section {
+    /// Top to bottom.
+    pub const ALL: [Section; 4] = [
+        Section::Home,
+        Section::Projects,
+        Section::Blogs,
+        Section::Photos,
+    ];
+
+    /// The `id` of the section's element.
+    pub fn id(self) -> &'static str {
+        match self {
+            Section::Home => "home",
+            Section::Projects => "projects",
+            Section::Blogs => "blogs",
+            Section::Photos => "photos",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Section::ALL.into_iter().find(|section| section.id() == id)
+    }
+}
+
+/// Where the site is served from, without a trailing slash; empty at the root.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BasePath(pub String);
+
+impl BasePath {
+    /// Normalizes `base` to a leading slash and no trailing one, so `home/` becomes `/home`.
+    pub fn new(base: &str) -> Self {
+        let base = base.trim_matches('/');
+        if base.is_empty() {
+            Self::default()
+        } else {
+            Self(format!("/{base}"))
+        }
+    }
+
+    /// The base path this build was made for.
+    pub fn configured() -> Self {
+        #[cfg(feature = "ssr")]
+        return Self::default();
+        #[cfg(not(feature = "ssr"))]
+        Self::new(option_env!("PORTFOLIO_BASE_PATH").unwrap_or_default())
+    }
+
+    /// The link to the route `path`.
+    pub fn path(&self, path: &str) -> String {
+        format!("{}/{}", self.0, path.trim_start_matches('/'))
+    }
+
+    /// The route path of the browser path `pathname`, so `/home/projects` under `/home` is
+    /// `/projects`; `None` outside the base path.
+    pub fn route<'a>(&self, pathname: &'a str) -> Option<&'a str> {
+        match pathname.strip_prefix(self.0.as_str())? {
+            "" => Some("/"),
+            rest if rest.starts_with('/') => Some(rest),
+            _ => None,
+        }
+    }
+
+    pub fn is_home(&self, pathname: &str) -> bool {
+        self.route(pathname) == Some("/")
+    }
+}
@@ -0,0 +1,166 @@
+//! Pack sizing behind the spot welder's battery calculator.
+
+use portfolio::battery::{
+    design_pack, top_face, Cell, Chemistry, PackError, Polarity, Strip, Target,
+    MAX_WELDS_PER_TERMINAL, PRESETS,
+};
+
+fn cell() -> Cell {
+    PRESETS[0].cell()
+}
+
+#[test]
+fn sizes_a_pack_from_the_target() {
+    let design = design_pack(
+        &cell(),
+        &Target {
+            voltage: 36.0,
+            capacity_ah: 10.0,
+        },
+        4,
+    )
+    .unwrap();
+    assert_eq!(
+        (design.series, design.parallel, design.cells()),
+        (10, 4, 40)
+    );
+    assert!((design.nominal_voltage - 36.0).abs() < 1e-9);
+    assert!((design.full_voltage - 42.0).abs() < 1e-9);
+    assert!((design.capacity_ah - 12.0).abs() < 1e-9);
+    assert!((design.energy_wh - 432.0).abs() < 1e-9);
+    assert_eq!(design.welds, 40 * 2 * 4);
+    assert!((design.cells_weight_g - 40.0 * 47.0).abs() < 1e-9);
+    assert!(
+        design.nickel_weight_g > 0.0
+            && design.total_weight_g > design.cells_weight_g + design.nickel_weight_g
+    );
+}
+
+#[test]
+fn rounds_up_to_reach_the_target() {
+    let lfp = Cell {
+        chemistry: Chemistry::LiFePo4,
+        capacity_mah: 3300.0,
+        voltage: 3.2,
+        weight_g: 86.0,
+        diameter_mm: 26.0,
+    };
+    let design = design_pack(
+        &lfp,
+        &Target {
+            voltage: 48.0,
+            capacity_ah: 20.0,
+        },
+        2,
+    )
+    .unwrap();
+    assert_eq!((design.series, design.parallel), (15, 7));
+    let design = design_pack(
+        &lfp,
+        &Target {
+            voltage: 50.0,
+            capacity_ah: 1.0,
+        },
+        2,
+    )
+    .unwrap();
+    assert_eq!((design.series, design.parallel), (16, 1));
+}
+
+#[test]
+fn rejects_unusable_inputs() {
+    let target = Target {
+        voltage: 36.0,
+        capacity_ah: 10.0,
+    };
+    let flat = Cell {
+        capacity_mah: 0.0,
+        ..cell()
+    };
+    assert_eq!(
+        design_pack(&flat, &target, 4),
+        Err(PackError::NotPositive("cell capacity"))
+    );
+    assert_eq!(
+        design_pack(
+            &cell(),
+            &Target {
+                voltage: f64::NAN,
+                ..target
+            },
+            4
+        ),
+        Err(PackError::NotPositive("pack voltage"))
+    );
+    assert!(matches!(
+        design_pack(&cell(), &target, 0),
+        Err(PackError::NotPositive(_))
+    ));
+    assert!(matches!(
+        design_pack(
+            &cell(),
+            &Target {
+                voltage: 400.0,
+                capacity_ah: 1000.0
+            },
+            4
+        ),
+        Err(PackError::TooManyCells(_))
+    ));
+    assert_eq!(
+        design_pack(&cell(), &target, usize::MAX),
+        Err(PackError::TooManyWelds(usize::MAX))
+    );
+    assert!(design_pack(&cell(), &target, MAX_WELDS_PER_TERMINAL).is_ok());
+}
+
+#[test]
+fn alternates_groups_on_the_top_face() {
+    let (polarities, strips) = top_face(4);
+    assert_eq!(
+        polarities,
+        [
+            Polarity::Negative,
+            Polarity::Positive,
+            Polarity::Negative,
+            Polarity::Positive
+        ]
+    );
+    assert_eq!(
+        strips,
+        [
+            Strip {
+                first: 0,
+                last: 0,
+                terminal: Some(Polarity::Negative)
+            },
+            Strip {
+                first: 1,
+                last: 2,
+                terminal: None
+            },
+            Strip {
+                first: 3,
+                last: 3,
+                terminal: Some(Polarity::Positive)
+            },
+        ]
+    );
+    // With an odd number of groups the positive terminal is on the bottom face.
+    let (_, strips) = top_face(5);
+    assert_eq!(
+        strips
+            .iter()
+            .filter(|strip| strip.terminal.is_some())
+            .count(),
+        1
+    );
+    assert_eq!(
+        strips.last(),
+        Some(&Strip {
+            first: 3,
+            last: 4,
+            terminal: None
+        })
+    );
+}
@@ -0,0 +1,87 @@
+//! Charts rendered to HTML, as the server sends them.
+
+use leptos::prelude::*;
+use portfolio::chart::SvgChart;
+use portfolio::data::{Chart, ChartKind, Series};
+
+fn render(chart: Chart) -> String {
+    let owner = Owner::new();
+    owner.with(|| view! { <SvgChart chart/> }.to_html())
+}
+
+fn count(html: &str, pattern: &str) -> usize {
+    html.matches(pattern).count()
+}
+
+fn compression() -> Chart {
+    Chart {
+        kind: ChartKind::Bar,
+        title: "Compression ratio".into(),
+        y_label: Some("Ratio".into()),
+        unit: Some("×".into()),
+        categories: vec!["Chromosome 1".into(), "Chromosome 2".into()].into(),
+        series: vec![
+            Series {
+                name: "Huffman".into(),
+                values: vec![4.0, 4.1].into(),
+                ..Default::default()
+            },
+            Series {
+                name: "DNAzip".into(),
+                values: vec![120.5, 98.0].into(),
+                ..Default::default()
+            },
+        ]
+        .into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn bar_charts_describe_themselves() {
+    let html = render(compression());
+    assert_eq!(count(&html, "<rect"), 4);
+    assert!(html.contains(r#"role="img""#));
+    assert!(
+        html.contains(
+            "Bar chart: Compression ratio, comparing Huffman, DNAzip across 2 categories."
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"aria-label="DNAzip, Chromosome 1: 120.5×""#),
+        "{html}"
+    );
+    // The data table has a row per category, with a cell per series.
+    assert!(
+        html.contains("<th scope=\"row\">Chromosome 2</th><td>4.1×</td><td>98×</td>"),
+        "{html}"
+    );
+    assert_eq!(count(&html, "chart-swatch"), 2);
+}
+
+#[test]
+fn line_and_scatter_charts_plot_points() {
+    let series = Series {
+        name: "Time".into(),
+        points: vec![[3.0, 1.5], [1.0, 0.5], [2.0, 1.0]].into(),
+        ..Default::default()
+    };
+    let line = Chart {
+        kind: ChartKind::Line,
+        series: vec![series.clone()].into(),
+        ..Default::default()
+    };
+    let html = render(line);
+    assert_eq!((count(&html, "<circle"), count(&html, "<polyline")), (3, 1));
+    assert!(!html.contains("chart-legend"));
+
+    let scatter = Chart {
+        kind: ChartKind::Scatter,
+        series: vec![series].into(),
+        ..Default::default()
+    };
+    let html = render(scatter);
+    assert_eq!((count(&html, "<circle"), count(&html, "<polyline")), (3, 0));
+    assert!(html.contains(r#"aria-label="Time, 2: 1""#), "{html}");
+}
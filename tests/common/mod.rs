@@ -0,0 +1,115 @@
+//! A local HTTP server standing in for the external hosts that content links to.
+
+// Each test crate that includes this module uses only part of it.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// What the server answers a request with.
+pub struct Reply {
+    status: &'static str,
+    headers: String,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    /// An empty response with `status`, like `404 Not Found`.
+    pub fn status(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: String::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            body: body.into(),
+            ..Self::status("200 OK")
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push_str(&format!("{name}: {value}\r\n"));
+        self
+    }
+}
+
+/// Answers every request with `respond(method, path)`, one thread per connection, and
+/// counts the requests for each path.
+#[derive(Clone)]
+pub struct Server {
+    base: String,
+    requests: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Server {
+    pub fn start(respond: impl Fn(&str, &str) -> Reply + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = Server {
+            base: format!("http://{}", listener.local_addr().unwrap()),
+            requests: Arc::default(),
+        };
+        let (handle, respond) = (server.clone(), Arc::new(respond));
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (handle, respond) = (handle.clone(), respond.clone());
+                thread::spawn(move || handle.serve(stream, &*respond));
+            }
+        });
+        server
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    pub fn requests(&self, path: &str) -> usize {
+        self.requests
+            .lock()
+            .unwrap()
+            .get(path)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn serve(&self, mut stream: TcpStream, respond: &dyn Fn(&str, &str) -> Reply) {
+        let mut request_line = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+        loop {
+            let mut header = String::new();
+            match reader.read_line(&mut header) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if header == "\r\n" => break,
+                Ok(_) => {}
+            }
+        }
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+        *self
+            .requests
+            .lock()
+            .unwrap()
+            .entry(path.to_string())
+            .or_default() += 1;
+
+        let reply = respond(method, path);
+        let head = format!(
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+            reply.status,
+            reply.headers,
+            reply.body.len()
+        );
+        let _ = stream.write_all(&[head.as_bytes(), &reply.body].concat());
+    }
+}
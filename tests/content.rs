@@ -0,0 +1,208 @@
+//! Checks over the built-in content, and of the checks themselves.
+
+use std::path::Path;
+
+use portfolio::content::{lint_content, Assets, Problem};
+use portfolio::data::{
+    all_blogs, all_projects, get_blogs, get_photos, get_projects, Block, Blog, Photo, Poster,
+    Project, Tone,
+};
+
+fn assets() -> Assets {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    Assets {
+        public_dir: root.join("public"),
+        media_dir: root.join("media"),
+    }
+}
+
+fn assert_clean(problems: Vec<Problem>) {
+    let report: Vec<String> = problems.iter().map(Problem::to_string).collect();
+    assert!(
+        report.is_empty(),
+        "content has problems:\n{}",
+        report.join("\n")
+    );
+}
+
+fn locations(problems: &[Problem]) -> Vec<&str> {
+    problems.iter().map(|p| p.location.as_str()).collect()
+}
+
+#[test]
+fn published_content_is_valid() {
+    assert_clean(lint_content(
+        &get_projects(),
+        &get_blogs(),
+        &get_photos(),
+        &assets(),
+    ));
+}
+
+#[test]
+fn unpublished_content_is_valid() {
+    assert_clean(lint_content(&all_projects(), &all_blogs(), &[], &assets()));
+}
+
+fn project(id: &str) -> Project {
+    Project {
+        id: id.into(),
+        title: "Title".into(),
+        ..all_projects().remove(0)
+    }
+}
+
+fn photo(url: &str, caption: &str, alt: &str) -> Photo {
+    Photo {
+        url: url.into(),
+        caption: caption.into(),
+        alt: alt.into(),
+    }
+}
+
+#[test]
+fn reports_every_problem_with_its_location() {
+    let mut broken = project("broken");
+    broken.live_link = Some("htps//example.com".into());
+    broken.code_link = Some("ftp://example.com/code".into());
+    broken.paper_link = Some("https://exa mple.com/paper.pdf".into());
+    broken.posters = Some(
+        vec![Poster {
+            name: "".into(),
+            url: "https://".into(),
+            ..Default::default()
+        }]
+        .into(),
+    );
+    broken.photos = Some(
+        vec![
+            photo("images/headshot.jpg", "Fine", "Fine"),
+            photo("images/missing.jpg", "", ""),
+            photo("https://example.com/photo.jpg", "Remote", "Remote"),
+        ]
+        .into(),
+    );
+
+    let projects = [
+        project("first"),
+        project("first"),
+        broken,
+        project("has space"),
+    ];
+    let blogs = [Blog {
+        live_link: Some("example.com".into()),
+        ..all_blogs().remove(0)
+    }];
+    let photos = [photo("images/temple-photo.jpg", "Temple", "")];
+
+    let problems = lint_content(&projects, &blogs, &photos, &assets());
+    assert_eq!(
+        locations(&problems),
+        [
+            "projects[1] (first).id",
+            "projects[2] (broken).live_link",
+            "projects[2] (broken).code_link",
+            "projects[2] (broken).paper_link",
+            "projects[2] (broken).posters[0].name",
+            "projects[2] (broken).posters[0].url",
+            "projects[2] (broken).photos[1].url",
+            "projects[2] (broken).photos[1].caption",
+            "projects[2] (broken).photos[1].alt",
+            "projects[2] (broken).photos[2].url",
+            "projects[3] (has space).id",
+            "blogs[0] (010526).live_link",
+            "photos[0].alt",
+        ],
+        "{problems:#?}"
+    );
+    assert_eq!(
+        problems[0].message,
+        "`first` is already used by projects[0]"
+    );
+    assert!(
+        problems[3].message.contains("whitespace"),
+        "{}",
+        problems[3]
+    );
+}
+
+#[test]
+fn accepts_local_links_and_media() {
+    let dir = std::env::temp_dir().join(format!("portfolio-lint-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("poster.pdf"), b"%PDF-").unwrap();
+    let assets = Assets {
+        media_dir: dir.clone(),
+        ..assets()
+    };
+
+    let mut local = project("local");
+    local.paper_link = Some("media/poster.pdf".into());
+    local.posters = Some(
+        vec![Poster {
+            name: "Poster".into(),
+            url: "/media/poster.pdf".into(),
+            ..Default::default()
+        }]
+        .into(),
+    );
+    local.photos = Some(vec![photo("/images/headshot.jpg", "Me", "A portrait")].into());
+    let problems = lint_content(&[local], &[], &[], &assets);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_clean(problems);
+}
+
+#[test]
+fn checks_content_blocks() {
+    let blocks: Vec<Block> = serde_json::from_str(
+        r#"[
+            {"type": "rich_text", "heading": "Results", "body": "**Faster**, with [notes](media/notes.pdf)."},
+            {"type": "image", "url": "images/missing.jpg", "caption": "Gone", "alt": "Gone"},
+            {"type": "gallery", "photos": []},
+            {"type": "metrics", "items": [{"value": "3×", "label": ""}]},
+            {"type": "video", "url": "https://www.youtube.com/watch?v=abc", "title": "Demo"},
+            {"type": "callout", "tone": "warning", "body": " "},
+            {"type": "chart", "categories": ["A", "B"], "series": [{"name": "One", "values": [1, 2]}, {"values": [1]}]}
+        ]"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        &blocks[5],
+        Block::Callout {
+            tone: Tone::Warning,
+            title: None,
+            ..
+        }
+    ));
+
+    let mut with_blocks = project("blocks");
+    with_blocks.blocks = Some(blocks.into());
+    let problems = lint_content(&[with_blocks], &[], &[], &assets());
+    assert_eq!(
+        locations(&problems),
+        [
+            "projects[0] (blocks).blocks[1].url",
+            "projects[0] (blocks).blocks[2].photos",
+            "projects[0] (blocks).blocks[3].items[0].label",
+            "projects[0] (blocks).blocks[5].body",
+            "projects[0] (blocks).blocks[6].series[1].name",
+            "projects[0] (blocks).blocks[6].series[1].values",
+        ],
+        "{problems:#?}"
+    );
+}
+
+#[test]
+fn checks_demo_keys() {
+    let mut known = project("known");
+    known.demo = Some("battery-pack".into());
+    let mut unknown = project("unknown");
+    unknown.demo = Some("flux-capacitor".into());
+    let problems = lint_content(&[known, unknown], &[], &[], &assets());
+    assert_eq!(
+        locations(&problems),
+        ["projects[1] (unknown).demo"],
+        "{problems:#?}"
+    );
+}
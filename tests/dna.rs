@@ -0,0 +1,115 @@
+//! The compression methods behind the GeneZippers demo.
+
+use portfolio::dna::{parse_fasta, vint_bits, FastaError, Huffman, ReferenceEncoding, Variant};
+
+/// Reproducible pseudo-random bases.
+fn bases(count: usize, mut state: u32) -> Vec<u8> {
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b"ACGT"[(state % 4) as usize]
+        })
+        .collect()
+}
+
+#[test]
+fn reads_fasta_and_bare_sequences() {
+    let records =
+        parse_fasta(">first sample\nACGT\nacgn\n; a comment\n\n>second\nTT GG\n").unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        (records[0].name.as_str(), records[0].sequence.as_slice()),
+        ("first sample", &b"ACGTACGN"[..])
+    );
+    assert_eq!(records[1].sequence, b"TTGG");
+
+    assert_eq!(parse_fasta("  acgt\n").unwrap()[0].sequence, b"ACGT");
+    assert_eq!(parse_fasta(">empty\n\n"), Err(FastaError::Empty));
+    assert_eq!(
+        parse_fasta(">x\nACGT\nAC*T\n"),
+        Err(FastaError::InvalidBase {
+            line: 3,
+            column: 3,
+            found: '*'
+        })
+    );
+}
+
+#[test]
+fn huffman_codes_frequent_bases_shorter() {
+    let sequence = [&b"A".repeat(8)[..], b"CCCC", b"GG", b"T"].concat();
+    let huffman = Huffman::build(&sequence);
+    let lengths: Vec<(char, usize)> = huffman
+        .table
+        .iter()
+        .map(|entry| (entry.symbol, entry.code.len()))
+        .collect();
+    assert_eq!(lengths, [('A', 1), ('C', 2), ('G', 3), ('T', 3)]);
+    assert_eq!(huffman.payload_bits(), 8 + 4 * 2 + 2 * 3 + 3);
+
+    let bits = huffman.encode(&sequence).unwrap();
+    assert_eq!(bits.len(), huffman.payload_bits());
+    assert_eq!(huffman.decode(&bits).unwrap(), sequence);
+    assert_eq!(huffman.encode(b"N"), None);
+
+    // A single symbol still needs one bit per base.
+    let single = Huffman::build(b"AAAA");
+    assert_eq!(single.encode(b"AAAA").unwrap(), "0000");
+}
+
+#[test]
+fn reference_encoding_finds_the_differences() {
+    let reference = bases(2000, 0x2545_f491);
+    let mut sequence = reference.clone();
+    sequence[100] = if sequence[100] == b'A' { b'C' } else { b'A' };
+    sequence.drain(900..904);
+    sequence.splice(500..500, b"GATTACA".iter().copied());
+    sequence.extend_from_slice(b"TTT");
+
+    let encoding = ReferenceEncoding::encode(&reference, &sequence);
+    assert_eq!(encoding.decode(&reference), sequence);
+    assert_eq!(encoding.variants.len(), 4, "{:?}", encoding.variants);
+    assert!(matches!(
+        encoding.variants[0],
+        Variant::Substitution { position: 100, .. }
+    ));
+    assert!(
+        matches!(&encoding.variants[1], Variant::Insertion { position: 500, bases } if bases == b"GATTACA")
+    );
+    // Where a deletion starts is ambiguous when the bases around it repeat.
+    assert!(matches!(
+        encoding.variants[2],
+        Variant::Deletion {
+            position: 907..=911,
+            length: 4
+        }
+    ));
+    assert!(matches!(&encoding.variants[3], Variant::Insertion { bases, .. } if bases == b"TTT"));
+    assert!(encoding.total_bits() < sequence.len() / 4);
+}
+
+#[test]
+fn reference_encoding_round_trips_unrelated_sequences() {
+    for (reference, sequence) in [
+        (bases(300, 1), bases(280, 2)),
+        (bases(50, 3), Vec::new()),
+        (Vec::new(), bases(50, 4)),
+        (bases(64, 5), bases(64, 5)),
+    ] {
+        let encoding = ReferenceEncoding::encode(&reference, &sequence);
+        assert_eq!(encoding.decode(&reference), sequence);
+    }
+    assert!(ReferenceEncoding::encode(b"ACGT", b"ACGT")
+        .variants
+        .is_empty());
+}
+
+#[test]
+fn positions_take_whole_bytes() {
+    assert_eq!(
+        [0, 127, 128, 16_383, 16_384].map(vint_bits),
+        [8, 8, 16, 16, 24]
+    );
+}
@@ -0,0 +1,244 @@
+//! The link checker against a local stand-in for the sites the content links to.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use portfolio::data::{all_blogs, all_projects, Blog, Poster, Project};
+use portfolio::links::{
+    check_links, collect_links, CheckOptions, Link, LinkCache, LinkReport, LinkStatus,
+};
+
+mod common;
+use common::{Reply, Server};
+
+/// The sites links point to, answering by path:
+///
+/// - `/ok`: 200
+/// - `/missing`: 404
+/// - `/moved`: 301 to `/ok`
+/// - `/no-head`: 405 to `HEAD`, 200 to `GET`
+/// - `/slow`: 200 after two seconds
+/// - `/busy/*`: 200 after 200 ms, tracking how many are served at once
+struct StandIn {
+    server: Server,
+    most_busy: Arc<AtomicUsize>,
+}
+
+impl StandIn {
+    fn start() -> Self {
+        let busy = Arc::new(AtomicUsize::new(0));
+        let most_busy = Arc::new(AtomicUsize::new(0));
+        let server = Server::start({
+            let most_busy = most_busy.clone();
+            move |method, path| match (method, path) {
+                (_, "/ok") => Reply::ok(""),
+                (_, "/moved") => Reply::status("301 Moved Permanently").header("Location", "/ok"),
+                ("HEAD", "/no-head") => Reply::status("405 Method Not Allowed"),
+                (_, "/no-head") => Reply::ok(""),
+                (_, "/slow") => {
+                    thread::sleep(Duration::from_secs(2));
+                    Reply::ok("")
+                }
+                (_, path) if path.starts_with("/busy/") => {
+                    let now = busy.fetch_add(1, Ordering::SeqCst) + 1;
+                    most_busy.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(200));
+                    busy.fetch_sub(1, Ordering::SeqCst);
+                    Reply::ok("")
+                }
+                _ => Reply::status("404 Not Found"),
+            }
+        });
+        StandIn { server, most_busy }
+    }
+
+    fn url(&self, path: &str) -> String {
+        self.server.url(path)
+    }
+
+    fn requests(&self, path: &str) -> usize {
+        self.server.requests(path)
+    }
+}
+
+fn link(url: String) -> Link {
+    Link {
+        locations: vec![format!("test ({url})")],
+        url: url.into(),
+    }
+}
+
+fn options() -> CheckOptions {
+    CheckOptions {
+        timeout: Duration::from_millis(500),
+        ..CheckOptions::default()
+    }
+}
+
+fn check(links: Vec<Link>, cache: &mut LinkCache, options: &CheckOptions) -> LinkReport {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime
+        .block_on(check_links(links, cache, options))
+        .unwrap()
+}
+
+fn statuses(report: &LinkReport) -> Vec<&LinkStatus> {
+    report.results.iter().map(|result| &result.status).collect()
+}
+
+#[test]
+fn collects_external_links_with_their_locations() {
+    let project = Project {
+        id: "a".into(),
+        live_link: Some("https://example.com/".into()),
+        code_link: Some("https://example.com/code".into()),
+        paper_link: Some("media/paper.pdf".into()),
+        posters: Some(
+            vec![Poster {
+                name: "Poster".into(),
+                url: "https://example.com/".into(),
+                ..Default::default()
+            }]
+            .into(),
+        ),
+        ..all_projects().remove(0)
+    };
+    let blog = Blog {
+        live_link: Some("http://example.org".into()),
+        ..all_blogs().remove(0)
+    };
+
+    let links = collect_links(&[project], &[blog]);
+    let urls: Vec<&str> = links.iter().map(|link| &*link.url).collect();
+    assert_eq!(
+        urls,
+        [
+            "https://example.com/",
+            "https://example.com/code",
+            "http://example.org"
+        ]
+    );
+    assert_eq!(
+        links[0].locations,
+        [
+            "projects[0] (a).live_link",
+            "projects[0] (a).posters[0].url"
+        ]
+    );
+    assert_eq!(links[2].locations, ["blogs[0] (010526).live_link"]);
+}
+
+#[test]
+fn reports_what_each_link_led_to() {
+    let server = StandIn::start();
+    let links =
+        ["/ok", "/missing", "/moved", "/no-head", "/slow"].map(|path| link(server.url(path)));
+
+    let report = check(links.to_vec(), &mut LinkCache::in_memory(), &options());
+    assert_eq!(
+        statuses(&report),
+        [
+            &LinkStatus::Ok {
+                status: 200,
+                redirected_to: None
+            },
+            &LinkStatus::Broken { status: 404 },
+            &LinkStatus::Ok {
+                status: 200,
+                redirected_to: Some(server.url("/ok"))
+            },
+            &LinkStatus::Ok {
+                status: 200,
+                redirected_to: None
+            },
+            &LinkStatus::Failed {
+                error: "timed out".to_string()
+            },
+        ]
+    );
+    assert_eq!(report.problems().count(), 2);
+    assert!(report
+        .to_string()
+        .contains(&format!("used in test ({})", server.url("/missing"))));
+}
+
+#[test]
+fn limits_requests_in_flight() {
+    let server = StandIn::start();
+    let links = (0..6)
+        .map(|i| link(server.url(&format!("/busy/{i}"))))
+        .collect();
+
+    let options = CheckOptions {
+        concurrency: 2,
+        ..options()
+    };
+    let report = check(links, &mut LinkCache::in_memory(), &options);
+    assert_eq!(report.problems().count(), 0);
+    assert_eq!(server.most_busy.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn reuses_cached_results() {
+    let server = StandIn::start();
+    let dir = std::env::temp_dir().join(format!("portfolio-links-{}", std::process::id()));
+    let path = dir.join("cache.json");
+    let links = || vec![link(server.url("/ok")), link(server.url("/missing"))];
+
+    let mut cache = LinkCache::load(&path).unwrap();
+    check(links(), &mut cache, &options());
+    cache.save().unwrap();
+    assert_eq!(
+        (server.requests("/ok"), server.requests("/missing")),
+        (1, 2)
+    );
+
+    // Working links come from the cache; broken ones are checked again.
+    let mut cache = LinkCache::load(&path).unwrap();
+    let report = check(links(), &mut cache, &options());
+    assert!(report.results[0].cached);
+    assert!(!report.results[1].cached);
+    assert_eq!(
+        (server.requests("/ok"), server.requests("/missing")),
+        (1, 4)
+    );
+
+    // Offline, everything comes from the cache and unknown links are reported as such.
+    let offline = CheckOptions {
+        offline: true,
+        ..options()
+    };
+    let mut all = links();
+    all.push(link(server.url("/never-seen")));
+    let report = check(all, &mut cache, &offline);
+    assert_eq!(
+        statuses(&report),
+        [
+            &LinkStatus::Ok {
+                status: 200,
+                redirected_to: None
+            },
+            &LinkStatus::Broken { status: 404 },
+            &LinkStatus::Unchecked,
+        ]
+    );
+    assert_eq!(
+        (server.requests("/ok"), server.requests("/never-seen")),
+        (1, 0)
+    );
+
+    // Results older than the maximum age are checked again.
+    let expired = CheckOptions {
+        max_age: chrono::Duration::zero(),
+        ..options()
+    };
+    check(links(), &mut cache, &expired);
+    assert_eq!(server.requests("/ok"), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
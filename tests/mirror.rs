@@ -0,0 +1,286 @@
+//! Mirroring posters and papers from a local stand-in for their original host.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use portfolio::data::{all_projects, Poster, Project};
+use portfolio::mirror::{
+    publish, update_store, upstream_urls, Manifest, MirrorOptions, MirrorOutcome, MirrorReport,
+    MIRROR_URL_DIR,
+};
+
+mod common;
+use common::{Reply, Server};
+
+/// Serves whatever `files` holds at a path, and 404 for anything else.
+struct Upstream {
+    server: Server,
+    files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl Upstream {
+    fn start() -> Self {
+        let files: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::default();
+        let server = Server::start({
+            let files = files.clone();
+            move |_, path| match files.lock().unwrap().get(path) {
+                Some(body) => Reply::ok(body.clone()),
+                None => Reply::status("404 Not Found"),
+            }
+        });
+        Upstream { server, files }
+    }
+
+    fn put(&self, path: &str, body: &[u8]) {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), body.to_vec());
+    }
+
+    fn url(&self, path: &str) -> String {
+        self.server.url(path)
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("portfolio-mirror-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn project(upstream: &Upstream) -> Project {
+    Project {
+        paper_link: Some(upstream.url("/paper.pdf").into()),
+        posters: Some(
+            vec![
+                Poster {
+                    name: "Poster".into(),
+                    url: upstream.url("/posters/Poster.pdf").into(),
+                    ..Default::default()
+                },
+                Poster {
+                    name: "Gone".into(),
+                    url: upstream.url("/gone.pdf").into(),
+                    ..Default::default()
+                },
+                Poster {
+                    name: "Local".into(),
+                    url: "media/local.pdf".into(),
+                    ..Default::default()
+                },
+            ]
+            .into(),
+        ),
+        ..all_projects().remove(0)
+    }
+}
+
+fn mirror(projects: &[Project], manifest: &mut Manifest, options: &MirrorOptions) -> MirrorReport {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime
+        .block_on(update_store(projects, manifest, options))
+        .unwrap()
+}
+
+fn outcomes(report: &MirrorReport) -> Vec<&MirrorOutcome> {
+    report
+        .results
+        .iter()
+        .map(|result| &result.outcome)
+        .collect()
+}
+
+#[test]
+fn mirrors_and_rewrites_links() {
+    let upstream = Upstream::start();
+    upstream.put("/paper.pdf", b"%PDF- paper");
+    upstream.put("/posters/Poster.pdf", b"%PDF- poster");
+    let (store, site) = (temp_dir("store"), temp_dir("site"));
+
+    let mut projects = vec![project(&upstream)];
+    let mut manifest = Manifest::load(&store).unwrap();
+    let report = mirror(&projects, &mut manifest, &MirrorOptions::default());
+    assert!(matches!(
+        outcomes(&report)[..],
+        [
+            MirrorOutcome::Added,
+            MirrorOutcome::Missing { .. },
+            MirrorOutcome::Added
+        ]
+    ));
+    manifest.save().unwrap();
+    publish(&mut projects, &manifest, &site).unwrap();
+
+    let posters = projects[0].posters.as_deref().unwrap();
+    assert!(
+        posters[0]
+            .url
+            .starts_with(&format!("{MIRROR_URL_DIR}/Poster-")),
+        "{}",
+        posters[0].url
+    );
+    assert_eq!(
+        posters[0].source.as_deref(),
+        Some(&*upstream.url("/posters/Poster.pdf"))
+    );
+    assert_eq!(
+        fs::read(site.join(&*posters[0].url)).unwrap(),
+        b"%PDF- poster"
+    );
+    assert_eq!(
+        (&*posters[1].url, posters[1].source.as_deref()),
+        (&*upstream.url("/gone.pdf"), None)
+    );
+    assert_eq!(
+        (&*posters[2].url, posters[2].source.as_deref()),
+        ("media/local.pdf", None)
+    );
+    assert_eq!(
+        projects[0].paper_source.as_deref(),
+        Some(&*upstream.url("/paper.pdf"))
+    );
+    assert_eq!(
+        fs::read(site.join(&**projects[0].paper_link.as_ref().unwrap())).unwrap(),
+        b"%PDF- paper"
+    );
+
+    // Rewritten content finds its upstream files through their sources.
+    assert_eq!(
+        upstream_urls(&projects),
+        upstream_urls(&[project(&upstream)])
+    );
+
+    fs::remove_dir_all(&store).unwrap();
+    fs::remove_dir_all(&site).unwrap();
+}
+
+#[test]
+fn flags_upstream_changes() {
+    let upstream = Upstream::start();
+    upstream.put("/paper.pdf", b"%PDF- paper");
+    upstream.put("/posters/Poster.pdf", b"%PDF- poster");
+    let store = temp_dir("changes");
+    let projects = [project(&upstream)];
+
+    let mut manifest = Manifest::load(&store).unwrap();
+    mirror(&projects, &mut manifest, &MirrorOptions::default());
+    manifest.save().unwrap();
+    let stored = manifest
+        .get(&upstream.url("/posters/Poster.pdf"))
+        .unwrap()
+        .clone();
+
+    // A changed file is reported, and the stored copy kept.
+    upstream.put("/posters/Poster.pdf", b"%PDF- poster, revised");
+    let mut manifest = Manifest::load(&store).unwrap();
+    let report = mirror(&projects, &mut manifest, &MirrorOptions::default());
+    assert!(matches!(
+        &outcomes(&report)[..],
+        [MirrorOutcome::Changed { stored: s, .. }, _, MirrorOutcome::Unchanged] if *s == stored.sha256
+    ));
+    assert_eq!(fs::read(store.join(&stored.file)).unwrap(), b"%PDF- poster");
+
+    // Until the change is accepted.
+    let accept = MirrorOptions {
+        accept_changes: true,
+        ..MirrorOptions::default()
+    };
+    let report = mirror(&projects, &mut manifest, &accept);
+    assert!(matches!(
+        outcomes(&report)[0],
+        MirrorOutcome::Updated { .. }
+    ));
+    assert_eq!(
+        fs::read(store.join(&stored.file)).unwrap(),
+        b"%PDF- poster, revised"
+    );
+
+    // A vanished upstream file keeps being served from the store.
+    upstream.files.lock().unwrap().clear();
+    let report = mirror(&projects, &mut manifest, &MirrorOptions::default());
+    assert!(matches!(
+        outcomes(&report)[..],
+        [
+            MirrorOutcome::Unavailable { .. },
+            MirrorOutcome::Missing { .. },
+            MirrorOutcome::Unavailable { .. }
+        ]
+    ));
+
+    fs::remove_dir_all(&store).unwrap();
+}
+
+#[test]
+fn refuses_a_stored_file_that_does_not_match_its_checksum() {
+    let upstream = Upstream::start();
+    upstream.put("/paper.pdf", b"%PDF- paper");
+    upstream.put("/posters/Poster.pdf", b"%PDF- poster");
+    let (store, site) = (temp_dir("corrupt"), temp_dir("corrupt-site"));
+    let mut projects = vec![project(&upstream)];
+
+    let mut manifest = Manifest::load(&store).unwrap();
+    mirror(&projects, &mut manifest, &MirrorOptions::default());
+    manifest.save().unwrap();
+    let stored = manifest
+        .get(&upstream.url("/paper.pdf"))
+        .unwrap()
+        .file
+        .clone();
+    fs::write(store.join(&stored), b"%PDF- tampered").unwrap();
+
+    let offline = MirrorOptions {
+        offline: true,
+        ..MirrorOptions::default()
+    };
+    let mut manifest = Manifest::load(&store).unwrap();
+    mirror(&projects, &mut manifest, &offline);
+    let err = publish(&mut projects, &manifest, &site).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(&store).unwrap();
+    fs::remove_dir_all(&site).unwrap();
+}
+
+#[test]
+fn leaves_web_pages_upstream() {
+    let upstream = Upstream::start();
+    upstream.put(
+        "/paper.html",
+        b"<!DOCTYPE html><link rel=stylesheet href=style.css>",
+    );
+    upstream.put("/posters/Poster.pdf", b"%PDF- poster");
+    let (store, site) = (temp_dir("pages"), temp_dir("pages-site"));
+    let mut projects = vec![Project {
+        paper_link: Some(upstream.url("/paper.html").into()),
+        ..project(&upstream)
+    }];
+
+    let mut manifest = Manifest::load(&store).unwrap();
+    let report = mirror(&projects, &mut manifest, &MirrorOptions::default());
+    assert!(matches!(
+        outcomes(&report)[..],
+        [
+            MirrorOutcome::Added,
+            MirrorOutcome::Missing { .. },
+            MirrorOutcome::NotPdf
+        ]
+    ));
+    assert!(!report.results[2].outcome.is_flagged());
+    assert!(manifest.get(&upstream.url("/paper.html")).is_none());
+
+    publish(&mut projects, &manifest, &site).unwrap();
+    assert_eq!(
+        projects[0].paper_link.as_deref(),
+        Some(&*upstream.url("/paper.html"))
+    );
+    assert_eq!(projects[0].paper_source, None);
+
+    fs::remove_dir_all(&store).unwrap();
+    fs::remove_dir_all(&site).unwrap();
+}
@@ -0,0 +1,44 @@
+//! The `Route` enum against the `Routes` table the app actually registers.
+
+use std::collections::BTreeSet;
+
+use portfolio::routing::{Route, Section};
+
+#[test]
+fn covers_the_routes_table() {
+    let table: BTreeSet<String> = leptos_axum::generate_route_list(portfolio::app::App)
+        .iter()
+        .map(|route| route.path().replace("{id}", ":id"))
+        .collect();
+
+    let routes = [
+        Route::Home,
+        Route::Section(Section::Projects),
+        Route::Projects,
+        Route::Project("genezippers".into()),
+        Route::Blog,
+        Route::BlogPost("first-post".into()),
+        Route::Photos,
+        Route::About,
+        Route::AdminLogin,
+        Route::Admin,
+        Route::AdminProjects,
+        Route::AdminProject("genezippers".into()),
+        Route::AdminProjectHistory("genezippers".into()),
+        Route::AdminBlogs,
+        Route::AdminBlog("first-post".into()),
+        Route::AdminBlogHistory("first-post".into()),
+        Route::AdminPhotos,
+        Route::AdminMedia,
+        Route::AdminAudit,
+    ];
+    let patterns: BTreeSet<String> = routes
+        .iter()
+        .map(|route| route.pattern().to_string())
+        .collect();
+    assert_eq!(table, patterns);
+
+    for route in &routes {
+        assert_eq!(Route::parse(&route.path()).as_ref(), Some(route));
+    }
+}
@@ -0,0 +1,140 @@
+//! Route paths under the base path the site is served from.
+
+use portfolio::content::ContentKind;
+use portfolio::routing::{BasePath, Route, Section};
+
+#[test]
+fn normalizes_the_base_path() {
+    assert_eq!(BasePath::new(""), BasePath::default());
+    assert_eq!(BasePath::new("/"), BasePath::default());
+    assert_eq!(BasePath::new("home/").0, "/home");
+    assert_eq!(BasePath::new("/home").0, "/home");
+}
+
+#[test]
+fn links_under_the_base_path() {
+    let root = BasePath::default();
+    assert_eq!(root.path("/"), "/");
+    assert_eq!(root.path("/projects"), "/projects");
+    assert_eq!(Route::Section(Section::Photos).href(&root), "/#photos");
+
+    let pages = BasePath::new("/home");
+    assert_eq!(pages.path("/"), "/home/");
+    assert_eq!(
+        pages.path("project/genezippers"),
+        "/home/project/genezippers"
+    );
+    assert_eq!(
+        Route::Section(Section::Photos).href(&pages),
+        "/home/#photos"
+    );
+    assert_eq!(
+        Route::Project("genezippers".into()).href(&pages),
+        "/home/project/genezippers"
+    );
+}
+
+#[test]
+fn strips_the_base_path() {
+    let pages = BasePath::new("/home");
+    assert_eq!(pages.route("/home"), Some("/"));
+    assert_eq!(pages.route("/home/"), Some("/"));
+    assert_eq!(
+        pages.route("/home/blog/first-post"),
+        Some("/blog/first-post")
+    );
+    assert_eq!(pages.route("/about"), None);
+    // Not under the base path, even though it starts the same.
+    assert_eq!(pages.route("/homework"), None);
+    assert_eq!(BasePath::default().route("/about"), Some("/about"));
+}
+
+#[test]
+fn recognizes_the_home_page() {
+    let pages = BasePath::new("/home");
+    assert!(pages.is_home("/home/"));
+    assert!(pages.is_home("/home"));
+    assert!(!pages.is_home("/"));
+    assert!(!pages.is_home("/home/about"));
+}
+
+/// One of each page, with ids where they take one.
+fn every_route() -> Vec<Route> {
+    let mut routes = vec![
+        Route::Home,
+        Route::Projects,
+        Route::Project("genezippers".into()),
+        Route::Blog,
+        Route::BlogPost("first-post".into()),
+        Route::Photos,
+        Route::About,
+        Route::AdminLogin,
+        Route::Admin,
+        Route::AdminProjects,
+        Route::AdminProject("genezippers".into()),
+        Route::AdminProjectHistory("genezippers".into()),
+        Route::AdminBlogs,
+        Route::AdminBlog("first-post".into()),
+        Route::AdminBlogHistory("first-post".into()),
+        Route::AdminPhotos,
+        Route::AdminMedia,
+        Route::AdminAudit,
+    ];
+    routes.extend(Section::ALL.map(Route::Section));
+    routes
+}
+
+#[test]
+fn parses_the_paths_it_builds() {
+    for route in every_route() {
+        assert_eq!(
+            Route::parse(&route.path()),
+            Some(route.clone()),
+            "{}",
+            route.path()
+        );
+    }
+}
+
+#[test]
+fn parses_paths_by_segment() {
+    assert_eq!(Route::parse("/"), Some(Route::Home));
+    assert_eq!(Route::parse(""), Some(Route::Home));
+    assert_eq!(
+        Route::parse("/#projects"),
+        Some(Route::Section(Section::Projects))
+    );
+    assert_eq!(Route::parse("/#nowhere"), Some(Route::Home));
+    assert_eq!(Route::parse("/blog/"), Some(Route::Blog));
+    assert_eq!(
+        Route::parse("/blog/first-post"),
+        Some(Route::BlogPost("first-post".into()))
+    );
+    assert_eq!(
+        Route::parse("/blog/first-post?preview=abc#top"),
+        Some(Route::BlogPost("first-post".into()))
+    );
+    assert_eq!(Route::parse("/blogs"), None);
+    assert_eq!(Route::parse("/project"), None);
+    assert_eq!(Route::parse("/project/genezippers/more"), None);
+}
+
+#[test]
+fn builds_content_routes_by_kind() {
+    assert_eq!(
+        Route::content(ContentKind::Project, "genezippers").path(),
+        "/project/genezippers"
+    );
+    assert_eq!(
+        Route::content(ContentKind::Blog, "first-post").path(),
+        "/blog/first-post"
+    );
+    assert_eq!(
+        Route::editor(ContentKind::Blog, "first-post").path(),
+        "/admin/blogs/first-post"
+    );
+    assert_eq!(
+        Route::history(ContentKind::Project, "genezippers").path(),
+        "/admin/projects/genezippers/history"
+    );
+}